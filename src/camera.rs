@@ -0,0 +1,89 @@
+//! A camera.
+
+use pyo3::prelude::*;
+
+use crate::Vector;
+
+#[pyclass]
+#[derive(Clone, Copy, Debug)]
+/// A camera, which determines which region of the world is shown on screen.
+/// 
+/// The camera's position is shown at the center of the frame.  A zoom greater
+/// than one magnifies the world, and the rotation (radians, counterclockwise)
+/// turns the camera, so that the world appears to turn clockwise.
+pub struct Camera {
+    #[pyo3(get, set)]
+    /// Position of the camera (world coordinates).
+    pub position: Vector,
+
+    #[pyo3(get, set)]
+    /// Zoom factor.
+    pub zoom: f64,
+
+    #[pyo3(get, set)]
+    /// Rotation (radians).
+    pub rotation: f64,
+}
+
+#[pymethods]
+impl Camera {
+    #[new]
+    #[pyo3(signature=(position=Vector::zero(), zoom=1.0, rotation=0.0))]
+    /// Construct a new camera.
+    pub fn new(position: Vector, zoom: f64, rotation: f64) -> Self {
+        Self {
+            position,
+            zoom,
+            rotation,
+        }
+    }
+}
+
+impl Camera {
+    /// Transform a point from world coordinates into screen coordinates.
+    /// 
+    /// Screen coordinates have their origin at the center of the frame.
+    pub fn transform(self, point: Vector) -> Vector {
+        // Offset from camera
+        let offset = point - self.position;
+
+        // Rotate opposite to the camera
+        let (sin, cos) = (-self.rotation).sin_cos();
+        let rotated = Vector::new(
+            offset.x * cos - offset.y * sin,
+            offset.x * sin + offset.y * cos,
+        );
+
+        rotated * self.zoom
+    }
+
+    /// Transform a point from screen coordinates back into world coordinates.
+    pub fn untransform(self, point: Vector) -> Vector {
+        // Undo zoom
+        let scaled = point * (1.0 / self.zoom);
+
+        // Rotate with the camera
+        let (sin, cos) = self.rotation.sin_cos();
+        let rotated = Vector::new(
+            scaled.x * cos - scaled.y * sin,
+            scaled.x * sin + scaled.y * cos,
+        );
+
+        rotated + self.position
+    }
+
+    /// Linearly interpolate between this camera and another.
+    pub fn lerp(self, other: Camera, progress: f64) -> Self {
+        Self {
+            position: self.position * (1.0 - progress) + other.position * progress,
+            zoom: self.zoom * (1.0 - progress) + other.zoom * progress,
+            rotation: self.rotation * (1.0 - progress) + other.rotation * progress,
+        }
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new(Vector::zero(), 1.0, 0.0)
+    }
+}
\ No newline at end of file
@@ -2,7 +2,13 @@
 
 use pyo3::prelude::*;
 
-use crate::Artist;
+use crate::{
+    Artist,
+    Clip,
+    ClipRegion,
+    Shape,
+    WipeDirection,
+};
 
 /// A video animation.
 /// 
@@ -12,6 +18,12 @@ pub trait Animate: Send + Sync {
     /// Creates an `Artist` trait object, given a progress value.
     fn play(&self, progress: f64) -> Box<dyn Artist>;
 
+    /// Prepares this animation to be rendered, given the size (world units) of a pixel
+    /// of the output video.  Most animations need no preparation.
+    fn prepare(&mut self, _pixel: f64) -> PyResult<()> {
+        Ok(())
+    }
+
     /// Clones this trait object.
     fn clone_box(&self) -> Box<dyn Animate>;
 
@@ -33,4 +45,30 @@ impl Clone for Animation {
     fn clone(&self) -> Self {
         Self (self.0.clone_box())
     }
+}
+
+#[pymethods]
+impl Animation {
+    /// Clip this animation to the inside of a shape, placed at the animation's location.
+    pub fn clip(&self, mask: Shape) -> Animation {
+        Clip::new(self.clone(), ClipRegion::Shape (Box::new(mask))).animate()
+    }
+
+    #[pyo3(signature=(direction=WipeDirection::LeftToRight, out=false))]
+    /// Reveal this animation with a wipe over the course of its progress
+    /// (`"left-to-right"`, `"right-to-left"`, `"bottom-to-top"`, `"top-to-bottom"`, or `"radial"`).
+    /// 
+    /// When wiping `out`, the animation is hidden instead.
+    pub fn wipe(&self, direction: WipeDirection, out: bool) -> Animation {
+        Clip::new(self.clone(), ClipRegion::Wipe { direction, out }).animate()
+    }
+
+    #[pyo3(signature=(path, width, out=false))]
+    /// Reveal this animation within `width` (world units) of a path, placed at the animation's
+    /// location, as the path is traced over the course of its progress.
+    /// 
+    /// When wiping `out`, the animation is hidden instead.
+    pub fn wipe_along(&self, path: Shape, width: f64, out: bool) -> Animation {
+        Clip::new(self.clone(), ClipRegion::Along { path: Box::new(path), width, out }).animate()
+    }
 }
\ No newline at end of file
@@ -1,13 +1,14 @@
 //! An image artist.
 
-use image::RgbImage;
-
-use crate::Vector;
+use crate::{
+    Frame,
+    Vector,
+};
 
 /// An image artist.
 /// 
 /// Image artists create visuals on a given frame.
 pub trait Artist {
     /// Draw on the given frame at the given location.
-    fn draw(&self, location: Vector, image: &mut RgbImage);
+    fn draw(&self, location: Vector, frame: &mut Frame);
 }
\ No newline at end of file
@@ -0,0 +1,165 @@
+//! Blending colors onto a frame.
+
+use image::{
+    Rgb,
+    Rgba,
+};
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    byte_to_linear,
+    linear_to_byte,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// How colors are blended onto a frame.
+///
+/// From Python, this is given as `"linear"` or `"legacy"`.
+pub enum Compositing {
+    /// Colors are blended in linear light, then converted to sRGB when the frame is saved.
+    Linear,
+
+    /// Gamma-encoded sRGB values are blended directly, truncating each term to a byte
+    /// (sRGB-space blending).
+    Legacy,
+}
+
+impl Compositing {
+    /// Convert a color into the encoding of a frame's image (fully opaque).
+    pub fn decode(self, color: Rgb<u8>) -> Rgba<f32> {
+        let [r, g, b] = match self {
+            Self::Linear => color.0.map(byte_to_linear),
+            Self::Legacy => color.0.map(|c| c as f32 / 255.0),
+        };
+
+        Rgba ([r, g, b, 1.0])
+    }
+
+    /// Convert an opaque pixel from the encoding of a frame's image into a color.
+    pub fn encode(self, pixel: Rgba<f32>) -> Rgb<u8> {
+        let rgb = [pixel[0], pixel[1], pixel[2]];
+
+        match self {
+            Self::Linear => Rgb (rgb.map(linear_to_byte)),
+            Self::Legacy => Rgb (rgb.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)),
+        }
+    }
+
+    /// Blend a color onto a pixel with a given strength and blend mode.
+    ///
+    /// Both are in the encoding of a frame's image, with premultiplied alpha.
+    pub fn blend(self, pixel: Rgba<f32>, color: Rgba<f32>, strength: f64, mode: BlendMode) -> Rgba<f32> {
+        // Opacity of the color being blended, and of the pixel beneath it
+        let alpha = (color[3] as f64 * strength) as f32;
+        let beneath = pixel[3];
+
+        if self == Self::Legacy && mode == BlendMode::Normal {
+            let strength = color[3] as f64 * strength;
+            let [r, g, b] = [0, 1, 2].map(|c| {
+                // Each term is truncated to a whole byte
+                let color = (color[c] / color[3].max(f32::MIN_POSITIVE) * 255.0).round() as f64 * strength;
+                let pixel = (pixel[c] * 255.0).round() as f64 * (1.0 - strength);
+
+                (color as u8).wrapping_add(pixel as u8) as f32 / 255.0
+            });
+
+            return Rgba ([r, g, b, alpha + beneath * (1.0 - alpha)]);
+        }
+
+        // Unpremultiplied colors
+        let unpremultiply = |value: f32, alpha: f32| if alpha > 0.0 { value / alpha } else { 0.0 };
+        let source = [0, 1, 2].map(|c| unpremultiply(color[c], color[3]));
+        let backdrop = [0, 1, 2].map(|c| unpremultiply(pixel[c], beneath));
+
+        // Blended color, where the pixel beneath is opaque
+        let blended = mode.apply(backdrop, source);
+
+        let [r, g, b] = [0, 1, 2].map(|c| {
+            let mixed = (1.0 - beneath) * source[c] + beneath * blended[c];
+            alpha * mixed + (1.0 - alpha) * pixel[c]
+        });
+
+        Rgba ([r, g, b, alpha + beneath * (1.0 - alpha)])
+    }
+}
+
+impl<'py> FromPyObject<'py> for Compositing {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "linear" => Ok(Self::Linear),
+            "legacy" => Ok(Self::Legacy),
+            name => Err(PyValueError::new_err(format!("unknown compositing '{}'", name))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+/// How a color is combined with the color beneath it.
+pub enum BlendMode {
+    #[default]
+    /// The color covers the color beneath it.
+    Normal,
+
+    /// The colors are added, brightening the frame (for glowing curves).
+    Add,
+
+    /// The inverted colors are multiplied, brightening the frame more gently than adding.
+    Screen,
+
+    /// The colors are multiplied, darkening the frame.
+    Multiply,
+
+    /// The lighter of the two colors is kept.
+    Lighten,
+
+    /// The darker color is subtracted from the lighter color.
+    Difference,
+}
+
+impl BlendMode {
+    /// Name of this blend mode.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Add => "add",
+            Self::Screen => "screen",
+            Self::Multiply => "multiply",
+            Self::Lighten => "lighten",
+            Self::Difference => "difference",
+        }
+    }
+
+    /// Combine a source color with the backdrop color beneath it.
+    pub fn apply(self, backdrop: [f32; 3], source: [f32; 3]) -> [f32; 3] {
+        [0, 1, 2].map(|c| {
+            let (b, s) = (backdrop[c], source[c]);
+
+            match self {
+                Self::Normal => s,
+                Self::Add => b + s,
+                Self::Screen => b + s - b * s,
+                Self::Multiply => b * s,
+                Self::Lighten => b.max(s),
+                Self::Difference => (b - s).abs(),
+            }
+        })
+    }
+}
+
+impl<'py> FromPyObject<'py> for BlendMode {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "normal" => Ok(Self::Normal),
+            "add" => Ok(Self::Add),
+            "screen" => Ok(Self::Screen),
+            "multiply" => Ok(Self::Multiply),
+            "lighten" => Ok(Self::Lighten),
+            "difference" => Ok(Self::Difference),
+            name => Err(PyValueError::new_err(format!("unknown blend mode '{}'", name))),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Clipping masks and wipes, which limit where an animation is drawn.
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    rasterize,
+    Animate,
+    Animation,
+    Artist,
+    Frame,
+    Shape,
+    Stroke,
+    Vector,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Direction in which a wipe reveals a drawing.
+///
+/// From Python, this is given as `"left-to-right"`, `"right-to-left"`, `"bottom-to-top"`,
+/// `"top-to-bottom"`, or `"radial"` (outwards from the center).
+pub enum WipeDirection {
+    /// The revealed part grows from the left edge of the drawing.
+    LeftToRight,
+
+    /// The revealed part grows from the right edge of the drawing.
+    RightToLeft,
+
+    /// The revealed part grows from the bottom edge of the drawing.
+    BottomToTop,
+
+    /// The revealed part grows from the top edge of the drawing.
+    TopToBottom,
+
+    /// The revealed part grows outwards from the center of the drawing.
+    Radial,
+}
+
+impl<'py> FromPyObject<'py> for WipeDirection {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "left-to-right" => Ok(Self::LeftToRight),
+            "right-to-left" => Ok(Self::RightToLeft),
+            "bottom-to-top" => Ok(Self::BottomToTop),
+            "top-to-bottom" => Ok(Self::TopToBottom),
+            "radial" => Ok(Self::Radial),
+            name => Err(PyValueError::new_err(format!("unknown wipe direction '{}'", name))),
+        }
+    }
+}
+
+#[derive(Clone)]
+/// Region of a frame that a clipped animation is drawn in.
+pub enum ClipRegion {
+    /// Inside of a shape (placed at the animation's location).
+    Shape (Box<Shape>),
+
+    /// Part of the drawing that a wipe has revealed (or, wiping out, not yet hidden).
+    Wipe {
+        direction: WipeDirection,
+        out: bool,
+    },
+
+    /// Part of the drawing within `width` (world units) of the traced part of a path
+    /// (placed at the animation's location).
+    Along {
+        path: Box<Shape>,
+        width: f64,
+        out: bool,
+    },
+}
+
+#[derive(Clone)]
+/// An animation that is clipped to a region, which may change with its progress.
+pub struct Clip {
+    /// Animation being clipped.
+    animation: Animation,
+
+    /// Region the animation is drawn in.
+    region: ClipRegion,
+}
+
+impl Clip {
+    /// Construct a new clipped animation.
+    pub fn new(animation: Animation, region: ClipRegion) -> Self {
+        Self {
+            animation,
+            region,
+        }
+    }
+}
+
+impl Animate for Clip {
+    fn play(&self, progress: f64) -> Box<dyn Artist> {
+        Box::new(Clipped {
+            artist: self.animation.0.play(progress),
+            region: self.region.clone(),
+            progress,
+        })
+    }
+
+    fn prepare(&mut self, pixel: f64) -> PyResult<()> {
+        self.animation.0.prepare(pixel)
+    }
+
+    fn clone_box(&self) -> Box<dyn Animate> {
+        Box::new(self.clone())
+    }
+}
+
+/// An artist that is clipped to a region.
+pub struct Clipped {
+    /// Artist being clipped.
+    artist: Box<dyn Artist>,
+
+    /// Region the artist is drawn in.
+    region: ClipRegion,
+
+    /// Progress of the animation, which moves wipes.
+    progress: f64,
+}
+
+impl Artist for Clipped {
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        // Draw offscreen, then keep only the part within the region
+        let mut offscreen = frame.offscreen();
+        self.artist.draw(location, &mut offscreen);
+
+        let Some ((x0, y0, x1, y1)) = offscreen.drawn() else {
+            return;
+        };
+
+        let coverage = self.coverage(&offscreen, location, (x0 as f64, y0 as f64, x1 as f64, y1 as f64));
+
+        for (x, y, pixel) in offscreen.image.enumerate_pixels_mut() {
+            if pixel[3] > 0.0 {
+                let coverage = coverage(x, y);
+                pixel.0 = pixel.0.map(|c| c * coverage);
+            }
+        }
+
+        frame.composite(&offscreen, 1.0, frame.blend);
+    }
+}
+
+impl Clipped {
+    /// Construct the coverage (between 0 and 1) of each pixel by this region, given the
+    /// pixel bounds of the drawing.
+    fn coverage<'a>(&'a self, frame: &Frame, location: Vector, bounds: (f64, f64, f64, f64)) -> Box<dyn Fn(u32, u32) -> f32 + 'a> {
+        let (x0, y0, x1, y1) = bounds;
+        let p = self.progress;
+
+        match &self.region {
+            ClipRegion::Shape (shape) => {
+                let polygons: Vec<Vec<Vector>> = shape.sized(frame).contours(location).iter()
+                    .map(|contour| contour.iter().map(|point| frame.to_pixels(*point)).collect())
+                    .collect();
+
+                match rasterize(&polygons, frame.image.width(), frame.image.height(), frame.antialias) {
+                    Some (mask) => Box::new(move |x, y| mask.get(x, y)),
+                    None => Box::new(|_, _| 0.0),
+                }
+            },
+            ClipRegion::Wipe { direction, out } => {
+                let (direction, out) = (*direction, *out);
+
+                // Center and half-diagonal of the drawing, for radial wipes
+                let (cx, cy) = (0.5 * (x0 + x1), 0.5 * (y0 + y1));
+                let reach = 0.5 * ((x1 - x0).powi(2) + (y1 - y0).powi(2)).sqrt();
+
+                Box::new(move |x, y| {
+                    let (x, y) = (x as f64, y as f64);
+
+                    // Revealed fraction of this pixel, with a soft edge one pixel wide
+                    let revealed = match direction {
+                        WipeDirection::LeftToRight => x0 + (x1 - x0) * p - x,
+                        WipeDirection::RightToLeft => x + 1.0 - (x1 - (x1 - x0) * p),
+                        WipeDirection::TopToBottom => y0 + (y1 - y0) * p - y,
+                        WipeDirection::BottomToTop => y + 1.0 - (y1 - (y1 - y0) * p),
+                        WipeDirection::Radial => reach * p - ((x + 0.5 - cx).powi(2) + (y + 0.5 - cy).powi(2)).sqrt() + 0.5,
+                    }.clamp(0.0, 1.0) as f32;
+
+                    if out { 1.0 - revealed } else { revealed }
+                })
+            },
+            ClipRegion::Along { path, width, out } => {
+                let out = *out;
+                let width = frame.line_width(*width);
+                let outline: Vec<Vec<Vector>> = traced(&path.sized(frame).contours(location), p).iter()
+                    .flat_map(|points| {
+                        let points: Vec<Vector> = points.iter().map(|point| frame.to_pixels(*point)).collect();
+                        Stroke::new(None).outline(&points, false, width)
+                    })
+                    .collect();
+
+                match rasterize(&outline, frame.image.width(), frame.image.height(), frame.antialias) {
+                    Some (mask) => Box::new(move |x, y| if out { 1.0 - mask.get(x, y) } else { mask.get(x, y) }),
+                    None => Box::new(move |_, _| if out { 1.0 } else { 0.0 }),
+                }
+            },
+        }
+    }
+}
+
+/// Find the parts of a series of contours traced (by length) up to some progress.
+fn traced(contours: &[Vec<Vector>], progress: f64) -> Vec<Vec<Vector>> {
+    let length = |points: &[Vector]| -> f64 { points.windows(2).map(|pair| (pair[1] - pair[0]).norm()).sum() };
+
+    let mut remaining = progress * contours.iter().map(|contour| length(contour)).sum::<f64>();
+    let mut parts = Vec::new();
+
+    for contour in contours {
+        if remaining <= 0.0 {
+            break;
+        }
+
+        let mut part = vec![contour[0]];
+        for pair in contour.windows(2) {
+            let step = (pair[1] - pair[0]).norm();
+
+            if step >= remaining {
+                part.push(pair[0] + (pair[1] - pair[0]) * (remaining / step));
+                remaining = 0.0;
+                break;
+            }
+
+            part.push(pair[1]);
+            remaining -= step;
+        }
+
+        parts.push(part);
+    }
+
+    parts
+}
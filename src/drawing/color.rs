@@ -0,0 +1,525 @@
+//! Colors, and color spaces for interpolating between them.
+
+use std::{
+    f64::consts::PI,
+    sync::OnceLock,
+};
+
+use image::Rgb;
+
+use pyo3::{
+    exceptions::{
+        PyTypeError,
+        PyValueError,
+    },
+    prelude::*,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// A color space in which colors are interpolated.
+pub enum ColorSpace {
+    /// Gamma-encoded sRGB values, blended directly.
+    Srgb,
+
+    /// Linear-light RGB values.
+    LinearRgb,
+
+    /// Hue, saturation, and value, with hue taking the shortest way around.
+    Hsv,
+
+    /// Hue, saturation, and lightness, with hue taking the shortest way around.
+    Hsl,
+
+    /// Oklab, a perceptually uniform space.
+    Oklab,
+
+    /// Lightness, chroma, and hue in Oklab, with hue taking the shortest way around.
+    Oklch,
+}
+
+impl ColorSpace {
+    /// Name of this color space.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Srgb => "srgb",
+            Self::LinearRgb => "linear",
+            Self::Hsv => "hsv",
+            Self::Hsl => "hsl",
+            Self::Oklab => "oklab",
+            Self::Oklch => "oklch",
+        }
+    }
+
+    /// Interpolate between two colors in this color space.
+    pub fn lerp(self, a: Rgb<u8>, b: Rgb<u8>, progress: f64) -> Rgb<u8> {
+        let mix = |x: f64, y: f64| x * (1.0 - progress) + y * progress;
+        let mix3 = |x: [f64; 3], y: [f64; 3]| [mix(x[0], y[0]), mix(x[1], y[1]), mix(x[2], y[2])];
+
+        match self {
+            Self::Srgb => Rgb (mix3(unit(a), unit(b)).map(to_byte)),
+            Self::LinearRgb => from_linear(mix3(to_linear(a), to_linear(b))),
+            Self::Hsv => {
+                let (x, y) = (rgb_to_hsv(unit(a)), rgb_to_hsv(unit(b)));
+                let (hx, hy) = hues(x[0], x[1], y[0], y[1]);
+                Rgb (hsv_to_rgb([mix_hue(hx, hy, progress), mix(x[1], y[1]), mix(x[2], y[2])]).map(to_byte))
+            },
+            Self::Hsl => {
+                let (x, y) = (rgb_to_hsl(unit(a)), rgb_to_hsl(unit(b)));
+                let (hx, hy) = hues(x[0], x[1], y[0], y[1]);
+                Rgb (hsl_to_rgb([mix_hue(hx, hy, progress), mix(x[1], y[1]), mix(x[2], y[2])]).map(to_byte))
+            },
+            Self::Oklab => from_linear(oklab_to_linear(mix3(linear_to_oklab(to_linear(a)), linear_to_oklab(to_linear(b))))),
+            Self::Oklch => {
+                let (x, y) = (lab_to_lch(linear_to_oklab(to_linear(a))), lab_to_lch(linear_to_oklab(to_linear(b))));
+                let (hx, hy) = hues(x[2], x[1], y[2], y[1]);
+                let lch = [mix(x[0], y[0]), mix(x[1], y[1]), mix_hue(hx, hy, progress)];
+                from_linear(oklab_to_linear(lch_to_lab(lch)))
+            },
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for ColorSpace {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "srgb" | "rgb" => Ok(Self::Srgb),
+            "linear" => Ok(Self::LinearRgb),
+            "hsv" => Ok(Self::Hsv),
+            "hsl" => Ok(Self::Hsl),
+            "oklab" => Ok(Self::Oklab),
+            "oklch" => Ok(Self::Oklch),
+            name => Err(PyValueError::new_err(format!("unknown color space '{}'", name))),
+        }
+    }
+}
+
+/// Convert a gamma-encoded sRGB value (between 0 and 1) to linear light.
+pub fn decode(value: f64) -> f64 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear light value (between 0 and 1) to gamma-encoded sRGB.
+pub fn encode(value: f64) -> f64 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Convert a byte of gamma-encoded sRGB to linear light, using a lookup table.
+pub fn byte_to_linear(value: u8) -> f32 {
+    static TABLE: OnceLock<[f32; 256]> = OnceLock::new();
+
+    TABLE.get_or_init(|| std::array::from_fn(|i| decode(i as f64 / 255.0) as f32))[value as usize]
+}
+
+/// Convert linear light to a byte of gamma-encoded sRGB.
+pub fn linear_to_byte(value: f32) -> u8 {
+    to_byte(encode((value as f64).clamp(0.0, 1.0)))
+}
+
+/// Convert a color to linear-light RGB values.
+pub fn to_linear(color: Rgb<u8>) -> [f64; 3] {
+    unit(color).map(decode)
+}
+
+/// Convert linear-light RGB values to a color.
+pub fn from_linear(rgb: [f64; 3]) -> Rgb<u8> {
+    Rgb (rgb.map(|c| to_byte(encode(c.clamp(0.0, 1.0)))))
+}
+
+/// Scale a color's channels to between 0 and 1.
+fn unit(color: Rgb<u8>) -> [f64; 3] {
+    color.0.map(|c| c as f64 / 255.0)
+}
+
+/// Scale a channel between 0 and 1 to a byte, rounding to the nearest value.
+fn to_byte(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Hues (radians) of two colors, borrowing the other color's hue if one has no saturation.
+fn hues(h1: f64, s1: f64, h2: f64, s2: f64) -> (f64, f64) {
+    const GRAY: f64 = 1E-6;
+
+    match (s1 < GRAY, s2 < GRAY) {
+        (true, false) => (h2, h2),
+        (false, true) => (h1, h1),
+        _ => (h1, h2),
+    }
+}
+
+/// Interpolate between two hues (radians), taking the shortest way around.
+fn mix_hue(h1: f64, h2: f64, progress: f64) -> f64 {
+    let delta = (h2 - h1 + PI).rem_euclid(2.0 * PI) - PI;
+
+    (h1 + delta * progress).rem_euclid(2.0 * PI)
+}
+
+/// Hue (radians), chroma, maximum, and minimum of RGB values.
+fn hue(rgb: [f64; 3]) -> (f64, f64, f64, f64) {
+    let [r, g, b] = rgb;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+
+    let sector = if chroma == 0.0 {
+        0.0
+    } else if max == r {
+        ((g - b) / chroma).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / chroma + 2.0
+    } else {
+        (r - g) / chroma + 4.0
+    };
+
+    (sector * PI / 3.0, chroma, max, min)
+}
+
+/// RGB values of a hue (radians) with a given chroma, offset by `m`.
+fn from_hue(hue: f64, chroma: f64, m: f64) -> [f64; 3] {
+    let sector = hue.rem_euclid(2.0 * PI) * 3.0 / PI;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+
+    let [r, g, b] = match sector as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+
+    [r + m, g + m, b + m]
+}
+
+/// Convert RGB values to hue (radians), saturation, and value.
+fn rgb_to_hsv(rgb: [f64; 3]) -> [f64; 3] {
+    let (h, chroma, max, _) = hue(rgb);
+
+    [h, if max > 0.0 { chroma / max } else { 0.0 }, max]
+}
+
+/// Convert hue (radians), saturation, and value to RGB values.
+fn hsv_to_rgb(hsv: [f64; 3]) -> [f64; 3] {
+    let [h, s, v] = hsv;
+    let chroma = v * s;
+
+    from_hue(h, chroma, v - chroma)
+}
+
+/// Convert RGB values to hue (radians), saturation, and lightness.
+fn rgb_to_hsl(rgb: [f64; 3]) -> [f64; 3] {
+    let (h, chroma, max, min) = hue(rgb);
+    let l = 0.5 * (max + min);
+    let s = if l > 0.0 && l < 1.0 { chroma / (1.0 - (2.0 * l - 1.0).abs()) } else { 0.0 };
+
+    [h, s, l]
+}
+
+/// Convert hue (radians), saturation, and lightness to RGB values.
+fn hsl_to_rgb(hsl: [f64; 3]) -> [f64; 3] {
+    let [h, s, l] = hsl;
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+
+    from_hue(h, chroma, l - 0.5 * chroma)
+}
+
+/// Convert linear-light RGB values to Oklab.
+fn linear_to_oklab(rgb: [f64; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb;
+
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+/// Convert Oklab to linear-light RGB values.
+fn oklab_to_linear(lab: [f64; 3]) -> [f64; 3] {
+    let [l, a, b] = lab;
+
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+    [
+        4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+        -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+        -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+    ]
+}
+
+/// Convert Oklab to lightness, chroma, and hue (radians).
+fn lab_to_lch(lab: [f64; 3]) -> [f64; 3] {
+    let [l, a, b] = lab;
+
+    [l, a.hypot(b), b.atan2(a)]
+}
+
+/// Convert lightness, chroma, and hue (radians) to Oklab.
+fn lch_to_lab(lch: [f64; 3]) -> [f64; 3] {
+    let [l, c, h] = lch;
+
+    [l, c * h.cos(), c * h.sin()]
+}
+
+#[pyclass(eq)]
+#[derive(Debug, PartialEq)]
+/// A color, given by its red, green, and blue channels.
+/// 
+/// Anywhere a color is taken, it may be given as a `Color`, a list of three channels,
+/// a hex string (`"#ff8800"` or `"#f80"`), or a CSS color name (`"orange"`).
+pub struct Color {
+    #[pyo3(get, set)]
+    /// Red channel.
+    pub r: u8,
+
+    #[pyo3(get, set)]
+    /// Green channel.
+    pub g: u8,
+
+    #[pyo3(get, set)]
+    /// Blue channel.
+    pub b: u8,
+}
+
+#[pymethods]
+impl Color {
+    #[new]
+    /// Construct a new color from its channels.
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            r,
+            g,
+            b,
+        }
+    }
+
+    #[staticmethod]
+    /// Parse a color from a hex string or CSS color name.
+    pub fn parse(text: &str) -> PyResult<Self> {
+        let text = text.trim();
+
+        // Hex string
+        if let Some (hex) = text.strip_prefix('#') {
+            let digits: Option<Vec<u8>> = hex.chars().map(|c| c.to_digit(16).map(|d| d as u8)).collect();
+
+            return match digits.as_deref() {
+                Some ([r, g, b]) => Ok(Self::new(r * 17, g * 17, b * 17)),
+                Some ([r1, r2, g1, g2, b1, b2]) => Ok(Self::new(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2)),
+                _ => Err(PyValueError::new_err(format!("invalid hex color '{}'", text))),
+            };
+        }
+
+        // Named color
+        let name = text.to_lowercase();
+        NAMED.iter()
+            .find(|(named, _)| *named == name)
+            .map(|(_, [r, g, b])| Self::new(*r, *g, *b))
+            .ok_or_else(|| PyValueError::new_err(format!("unknown color '{}'", text)))
+    }
+
+    #[getter]
+    /// Hex string of this color.
+    pub fn get_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("Color({}, {}, {})", self.r, self.g, self.b)
+    }
+}
+
+impl Color {
+    /// Convert this color into an RGB pixel.
+    pub fn rgb(&self) -> Rgb<u8> {
+        Rgb ([self.r, self.g, self.b])
+    }
+}
+
+impl From<Rgb<u8>> for Color {
+    fn from(color: Rgb<u8>) -> Self {
+        Self::new(color[0], color[1], color[2])
+    }
+}
+
+impl<'py> FromPyObject<'py> for Color {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok (color) = ob.downcast::<Color>() {
+            let color = color.borrow();
+            return Ok(Self::new(color.r, color.g, color.b));
+        }
+
+        if let Ok (text) = ob.extract::<String>() {
+            return Self::parse(&text);
+        }
+
+        match ob.extract::<[u8; 3]>() {
+            Ok ([r, g, b]) => Ok(Self::new(r, g, b)),
+            Err (_) => Err(PyTypeError::new_err("expected a Color, a list of three channels, a hex string, or a color name")),
+        }
+    }
+}
+
+/// CSS named colors.
+const NAMED: [(&str, [u8; 3]); 148] = [
+    ("aliceblue", [240, 248, 255]),
+    ("antiquewhite", [250, 235, 215]),
+    ("aqua", [0, 255, 255]),
+    ("aquamarine", [127, 255, 212]),
+    ("azure", [240, 255, 255]),
+    ("beige", [245, 245, 220]),
+    ("bisque", [255, 228, 196]),
+    ("black", [0, 0, 0]),
+    ("blanchedalmond", [255, 235, 205]),
+    ("blue", [0, 0, 255]),
+    ("blueviolet", [138, 43, 226]),
+    ("brown", [165, 42, 42]),
+    ("burlywood", [222, 184, 135]),
+    ("cadetblue", [95, 158, 160]),
+    ("chartreuse", [127, 255, 0]),
+    ("chocolate", [210, 105, 30]),
+    ("coral", [255, 127, 80]),
+    ("cornflowerblue", [100, 149, 237]),
+    ("cornsilk", [255, 248, 220]),
+    ("crimson", [220, 20, 60]),
+    ("cyan", [0, 255, 255]),
+    ("darkblue", [0, 0, 139]),
+    ("darkcyan", [0, 139, 139]),
+    ("darkgoldenrod", [184, 134, 11]),
+    ("darkgray", [169, 169, 169]),
+    ("darkgreen", [0, 100, 0]),
+    ("darkgrey", [169, 169, 169]),
+    ("darkkhaki", [189, 183, 107]),
+    ("darkmagenta", [139, 0, 139]),
+    ("darkolivegreen", [85, 107, 47]),
+    ("darkorange", [255, 140, 0]),
+    ("darkorchid", [153, 50, 204]),
+    ("darkred", [139, 0, 0]),
+    ("darksalmon", [233, 150, 122]),
+    ("darkseagreen", [143, 188, 143]),
+    ("darkslateblue", [72, 61, 139]),
+    ("darkslategray", [47, 79, 79]),
+    ("darkslategrey", [47, 79, 79]),
+    ("darkturquoise", [0, 206, 209]),
+    ("darkviolet", [148, 0, 211]),
+    ("deeppink", [255, 20, 147]),
+    ("deepskyblue", [0, 191, 255]),
+    ("dimgray", [105, 105, 105]),
+    ("dimgrey", [105, 105, 105]),
+    ("dodgerblue", [30, 144, 255]),
+    ("firebrick", [178, 34, 34]),
+    ("floralwhite", [255, 250, 240]),
+    ("forestgreen", [34, 139, 34]),
+    ("fuchsia", [255, 0, 255]),
+    ("gainsboro", [220, 220, 220]),
+    ("ghostwhite", [248, 248, 255]),
+    ("gold", [255, 215, 0]),
+    ("goldenrod", [218, 165, 32]),
+    ("gray", [128, 128, 128]),
+    ("green", [0, 128, 0]),
+    ("greenyellow", [173, 255, 47]),
+    ("grey", [128, 128, 128]),
+    ("honeydew", [240, 255, 240]),
+    ("hotpink", [255, 105, 180]),
+    ("indianred", [205, 92, 92]),
+    ("indigo", [75, 0, 130]),
+    ("ivory", [255, 255, 240]),
+    ("khaki", [240, 230, 140]),
+    ("lavender", [230, 230, 250]),
+    ("lavenderblush", [255, 240, 245]),
+    ("lawngreen", [124, 252, 0]),
+    ("lemonchiffon", [255, 250, 205]),
+    ("lightblue", [173, 216, 230]),
+    ("lightcoral", [240, 128, 128]),
+    ("lightcyan", [224, 255, 255]),
+    ("lightgoldenrodyellow", [250, 250, 210]),
+    ("lightgray", [211, 211, 211]),
+    ("lightgreen", [144, 238, 144]),
+    ("lightgrey", [211, 211, 211]),
+    ("lightpink", [255, 182, 193]),
+    ("lightsalmon", [255, 160, 122]),
+    ("lightseagreen", [32, 178, 170]),
+    ("lightskyblue", [135, 206, 250]),
+    ("lightslategray", [119, 136, 153]),
+    ("lightslategrey", [119, 136, 153]),
+    ("lightsteelblue", [176, 196, 222]),
+    ("lightyellow", [255, 255, 224]),
+    ("lime", [0, 255, 0]),
+    ("limegreen", [50, 205, 50]),
+    ("linen", [250, 240, 230]),
+    ("magenta", [255, 0, 255]),
+    ("maroon", [128, 0, 0]),
+    ("mediumaquamarine", [102, 205, 170]),
+    ("mediumblue", [0, 0, 205]),
+    ("mediumorchid", [186, 85, 211]),
+    ("mediumpurple", [147, 112, 219]),
+    ("mediumseagreen", [60, 179, 113]),
+    ("mediumslateblue", [123, 104, 238]),
+    ("mediumspringgreen", [0, 250, 154]),
+    ("mediumturquoise", [72, 209, 204]),
+    ("mediumvioletred", [199, 21, 133]),
+    ("midnightblue", [25, 25, 112]),
+    ("mintcream", [245, 255, 250]),
+    ("mistyrose", [255, 228, 225]),
+    ("moccasin", [255, 228, 181]),
+    ("navajowhite", [255, 222, 173]),
+    ("navy", [0, 0, 128]),
+    ("oldlace", [253, 245, 230]),
+    ("olive", [128, 128, 0]),
+    ("olivedrab", [107, 142, 35]),
+    ("orange", [255, 165, 0]),
+    ("orangered", [255, 69, 0]),
+    ("orchid", [218, 112, 214]),
+    ("palegoldenrod", [238, 232, 170]),
+    ("palegreen", [152, 251, 152]),
+    ("paleturquoise", [175, 238, 238]),
+    ("palevioletred", [219, 112, 147]),
+    ("papayawhip", [255, 239, 213]),
+    ("peachpuff", [255, 218, 185]),
+    ("peru", [205, 133, 63]),
+    ("pink", [255, 192, 203]),
+    ("plum", [221, 160, 221]),
+    ("powderblue", [176, 224, 230]),
+    ("purple", [128, 0, 128]),
+    ("rebeccapurple", [102, 51, 153]),
+    ("red", [255, 0, 0]),
+    ("rosybrown", [188, 143, 143]),
+    ("royalblue", [65, 105, 225]),
+    ("saddlebrown", [139, 69, 19]),
+    ("salmon", [250, 128, 114]),
+    ("sandybrown", [244, 164, 96]),
+    ("seagreen", [46, 139, 87]),
+    ("seashell", [255, 245, 238]),
+    ("sienna", [160, 82, 45]),
+    ("silver", [192, 192, 192]),
+    ("skyblue", [135, 206, 235]),
+    ("slateblue", [106, 90, 205]),
+    ("slategray", [112, 128, 144]),
+    ("slategrey", [112, 128, 144]),
+    ("snow", [255, 250, 250]),
+    ("springgreen", [0, 255, 127]),
+    ("steelblue", [70, 130, 180]),
+    ("tan", [210, 180, 140]),
+    ("teal", [0, 128, 128]),
+    ("thistle", [216, 191, 216]),
+    ("tomato", [255, 99, 71]),
+    ("turquoise", [64, 224, 208]),
+    ("violet", [238, 130, 238]),
+    ("wheat", [245, 222, 179]),
+    ("white", [255, 255, 255]),
+    ("whitesmoke", [245, 245, 245]),
+    ("yellow", [255, 255, 0]),
+    ("yellowgreen", [154, 205, 50]),
+];
@@ -0,0 +1,211 @@
+//! Image effects, applied to drawings before they are composited onto a frame.
+
+use image::{
+    Rgb,
+    Rgba,
+    Rgba32FImage,
+};
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    drawn,
+    Color,
+    Frame,
+    Vector,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Kind of an image effect.
+pub enum EffectKind {
+    /// A Gaussian blur.
+    Blur {
+        radius: f64,
+    },
+
+    /// A blurred halo beneath the drawing, brightened by `strength`.
+    Glow {
+        radius: f64,
+        strength: f64,
+    },
+
+    /// A blurred, colored shadow beneath the drawing, moved by `offset` (world units).
+    Shadow {
+        offset: Vector,
+        radius: f64,
+        color: Rgb<u8>,
+        opacity: f64,
+    },
+}
+
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+/// An image effect, applied to a drawing before it is composited onto a frame.
+///
+/// Effects are applied to an animation or a whole layer, which is drawn on its
+/// own offscreen buffer first.  All radii and offsets are in world units.
+pub struct Effect {
+    /// Kind of this effect.
+    pub kind: EffectKind,
+}
+
+#[pymethods]
+impl Effect {
+    #[staticmethod]
+    /// Construct a Gaussian blur, spreading each point over about `radius`.
+    pub fn blur(radius: f64) -> PyResult<Self> {
+        Self::new(EffectKind::Blur { radius })
+    }
+
+    #[staticmethod]
+    #[pyo3(signature=(radius, strength=1.0))]
+    /// Construct a glow (bloom), adding a blurred halo of the drawing beneath it.
+    pub fn glow(radius: f64, strength: f64) -> PyResult<Self> {
+        if strength < 0.0 {
+            return Err(PyValueError::new_err("glow strength must not be negative"));
+        }
+
+        Self::new(EffectKind::Glow { radius, strength })
+    }
+
+    #[staticmethod]
+    #[pyo3(signature=(offset, radius=4.0, color=None, opacity=0.5))]
+    /// Construct a drop shadow, moved by `offset` and blurred by `radius`.
+    ///
+    /// Shadows are black unless a color is given.
+    pub fn shadow(offset: Vector, radius: f64, color: Option<Color>, opacity: f64) -> PyResult<Self> {
+        Self::new(EffectKind::Shadow {
+            offset,
+            radius,
+            color: color.map_or(Rgb ([0, 0, 0]), |color| color.rgb()),
+            opacity: opacity.clamp(0.0, 1.0),
+        })
+    }
+}
+
+impl Effect {
+    /// Construct a new effect, checking its radius.
+    fn new(kind: EffectKind) -> PyResult<Self> {
+        let radius = match kind {
+            EffectKind::Blur { radius } | EffectKind::Glow { radius, .. } | EffectKind::Shadow { radius, .. } => radius,
+        };
+
+        if radius < 0.0 {
+            return Err(PyValueError::new_err("effect radius must not be negative"));
+        }
+
+        Ok(Self {
+            kind,
+        })
+    }
+
+    /// Apply this effect to an offscreen frame.
+    pub fn apply(&self, frame: &mut Frame) {
+        // Pixels per world unit
+        let scale = frame.camera.zoom * frame.scale;
+
+        match self.kind {
+            EffectKind::Blur { radius } => {
+                frame.image = blur(&frame.image, radius * scale / 2.0);
+            },
+            EffectKind::Glow { radius, strength } => {
+                let mut halo = blur(&frame.image, radius * scale / 2.0);
+                for pixel in halo.pixels_mut() {
+                    pixel.0 = pixel.0.map(|c| c * strength as f32);
+                    pixel[3] = pixel[3].min(1.0);
+                }
+
+                frame.image = over(&frame.image, &halo);
+            },
+            EffectKind::Shadow { offset, radius, color, opacity } => {
+                // Offset in pixels (accounting for the camera's rotation)
+                let shift = frame.to_pixels(offset) - frame.to_pixels(Vector::zero());
+                let (dx, dy) = (shift.x.round() as i64, shift.y.round() as i64);
+
+                // Silhouette of the drawing, in the shadow's color
+                let color = frame.compositing.decode(color);
+                let (width, height) = frame.image.dimensions();
+                let silhouette = Rgba32FImage::from_fn(width, height, |x, y| {
+                    let (sx, sy) = (x as i64 - dx, y as i64 - dy);
+
+                    if sx < 0 || sy < 0 || sx >= width as i64 || sy >= height as i64 {
+                        return Rgba ([0.0; 4]);
+                    }
+
+                    let alpha = frame.image.get_pixel(sx as u32, sy as u32)[3] * opacity as f32;
+                    Rgba ([color[0] * alpha, color[1] * alpha, color[2] * alpha, alpha])
+                });
+
+                frame.image = over(&frame.image, &blur(&silhouette, radius * scale / 2.0));
+            },
+        }
+    }
+}
+
+/// Place one image (with premultiplied alpha) over another.
+fn over(top: &Rgba32FImage, bottom: &Rgba32FImage) -> Rgba32FImage {
+    Rgba32FImage::from_fn(top.width(), top.height(), |x, y| {
+        let (top, bottom) = (top.get_pixel(x, y), bottom.get_pixel(x, y));
+
+        Rgba ([0, 1, 2, 3].map(|c| top[c] + bottom[c] * (1.0 - top[3])))
+    })
+}
+
+/// Blur an image (with premultiplied alpha) with a Gaussian of standard deviation `sigma` (pixels).
+fn blur(image: &Rgba32FImage, sigma: f64) -> Rgba32FImage {
+    if sigma < 0.1 {
+        return image.clone();
+    }
+
+    // Normalized kernel, reaching three standard deviations
+    let reach = (3.0 * sigma).ceil() as i64;
+    let mut kernel: Vec<f32> = (-reach..=reach).map(|i| (-(i * i) as f64 / (2.0 * sigma * sigma)).exp() as f32).collect();
+    let total: f32 = kernel.iter().sum();
+    kernel.iter_mut().for_each(|weight| *weight /= total);
+
+    // Only the region around the drawing needs blurring
+    let Some ((x0, y0, x1, y1)) = drawn(image) else {
+        return image.clone();
+    };
+
+    let grow = |value: u32, by: i64, limit: u32| (value as i64 + by).clamp(0, limit as i64) as u32;
+    let (x0, y0) = (grow(x0, -reach, image.width()), grow(y0, -reach, image.height()));
+    let (x1, y1) = (grow(x1, reach, image.width()), grow(y1, reach, image.height()));
+
+    let horizontal = convolve(image, &kernel, (x0, y0, x1, y1), true);
+    convolve(&horizontal, &kernel, (x0, y0, x1, y1), false)
+}
+
+/// Convolve a region of an image with a kernel, either horizontally or vertically.
+fn convolve(image: &Rgba32FImage, kernel: &[f32], region: (u32, u32, u32, u32), horizontal: bool) -> Rgba32FImage {
+    let reach = (kernel.len() / 2) as i64;
+    let (x0, y0, x1, y1) = region;
+    let mut output = Rgba32FImage::new(image.width(), image.height());
+
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let mut sum = [0.0f32; 4];
+
+            for (k, weight) in kernel.iter().enumerate() {
+                let offset = k as i64 - reach;
+                let (sx, sy) = if horizontal { (x as i64 + offset, y as i64) } else { (x as i64, y as i64 + offset) };
+
+                if sx < x0 as i64 || sy < y0 as i64 || sx >= x1 as i64 || sy >= y1 as i64 {
+                    continue;
+                }
+
+                let pixel = image.get_pixel(sx as u32, sy as u32);
+                for c in 0..4 {
+                    sum[c] += pixel[c] * weight;
+                }
+            }
+
+            output.put_pixel(x, y, Rgba (sum));
+        }
+    }
+
+    output
+}
@@ -0,0 +1,286 @@
+//! A video frame.
+
+use image::{
+    Rgb,
+    Rgba,
+    Rgba32FImage,
+    RgbImage,
+};
+
+use crate::{
+    add_pixel,
+    drawn,
+    rasterize,
+    AlongStroke,
+    Artist,
+    BlendMode,
+    Camera,
+    Compositing,
+    Effect,
+    Mask,
+    Paint,
+    Stroke,
+    Theme,
+    Vector,
+};
+
+/// A video frame that artists draw on.
+/// 
+/// The frame holds the image being drawn along with the camera and scale,
+/// which determine how world coordinates are mapped onto pixels.  When
+/// supersampling, the image is larger than the output frame, and the scale
+/// is increased to match.
+pub struct Frame {
+    /// Image being drawn, encoded according to the compositing (linear light,
+    /// unless compositing the legacy way), with premultiplied alpha.
+    pub image: Rgba32FImage,
+
+    /// Camera viewing this frame.
+    pub camera: Camera,
+
+    /// Number of pixels per world unit.
+    pub scale: f64,
+
+    /// Size (world units) of a pixel of the output video, or `None` if geometry is
+    /// measured in pixels.
+    pub pixel: Option<f64>,
+
+    /// Are lines anti-aliased?
+    pub antialias: bool,
+
+    /// How colors are blended onto this frame.
+    pub compositing: Compositing,
+
+    /// Theme giving the colors and thickness of objects that don't specify their own.
+    pub theme: Theme,
+
+    /// How colors being drawn are combined with the colors beneath them.
+    pub blend: BlendMode,
+}
+
+impl Frame {
+    /// Construct a new frame, filled with the theme's background color.
+    pub fn new(size: (u32, u32), theme: Theme, camera: Camera, scale: f64, pixel: Option<f64>, antialias: bool, compositing: Compositing) -> Self {
+        Self {
+            image: Rgba32FImage::from_pixel(size.0, size.1, compositing.decode(theme.background)),
+            camera,
+            scale,
+            pixel,
+            antialias,
+            compositing,
+            theme,
+            blend: BlendMode::Normal,
+        }
+    }
+
+    /// Construct a transparent offscreen frame matching this one, for drawing
+    /// that is composited onto this frame later.
+    pub fn offscreen(&self) -> Self {
+        Self {
+            image: Rgba32FImage::new(self.image.width(), self.image.height()),
+            camera: self.camera,
+            scale: self.scale,
+            pixel: self.pixel,
+            antialias: self.antialias,
+            compositing: self.compositing,
+            theme: self.theme.clone(),
+            blend: BlendMode::Normal,
+        }
+    }
+
+    /// Composite an offscreen frame onto this frame with an opacity and blend mode.
+    pub fn composite(&mut self, offscreen: &Frame, opacity: f64, mode: BlendMode) {
+        for (x, y, pixel) in offscreen.image.enumerate_pixels() {
+            if pixel[3] > 0.0 {
+                add_pixel(&mut self.image, x, y, *pixel, opacity, self.compositing, mode);
+            }
+        }
+    }
+
+    /// Draw an artist at a location on this frame with a blend mode.
+    /// 
+    /// An artist with effects is drawn on an offscreen frame, which the effects
+    /// are applied to before it is composited onto this frame.
+    pub fn draw(&mut self, artist: &dyn Artist, location: Vector, blend: BlendMode, effects: &[Effect]) {
+        if effects.is_empty() {
+            self.blend = blend;
+            artist.draw(location, self);
+            return;
+        }
+
+        let mut offscreen = self.offscreen();
+        artist.draw(location, &mut offscreen);
+
+        for effect in effects {
+            effect.apply(&mut offscreen);
+        }
+
+        self.composite(&offscreen, 1.0, blend);
+    }
+
+    /// Find the pixel region `(x0, y0, x1, y1)` that has been drawn on, if this frame is
+    /// offscreen (and so starts transparent).
+    pub fn drawn(&self) -> Option<(u32, u32, u32, u32)> {
+        drawn(&self.image)
+    }
+
+    /// Convert a point in world coordinates into pixel coordinates.
+    /// 
+    /// Pixel `(x, y)` covers the square from `(x, y)` to `(x + 1, y + 1)`.  Points
+    /// outside the frame are not constrained to the frame.
+    pub fn to_pixels(&self, point: Vector) -> Vector {
+        let screen = self.camera.transform(point) * self.scale;
+
+        Vector::new(
+            screen.x + (self.image.width() as f64)/2.0 + 0.5,
+            (self.image.height() as f64)/2.0 - screen.y + 0.5,
+        )
+    }
+
+    /// Convert a point in pixel coordinates back into world coordinates.
+    pub fn to_world(&self, pixel: Vector) -> Vector {
+        let screen = Vector::new(
+            pixel.x - (self.image.width() as f64)/2.0 - 0.5,
+            (self.image.height() as f64)/2.0 + 0.5 - pixel.y,
+        );
+
+        self.camera.untransform(screen * (1.0 / self.scale))
+    }
+
+    /// Color of an object, using the theme's foreground color if it doesn't specify one.
+    pub fn color(&self, color: Option<Rgb<u8>>) -> Rgb<u8> {
+        color.unwrap_or(self.theme.foreground)
+    }
+
+    /// Thickness (world units) of an object, using the theme's thickness (pixels) if it
+    /// doesn't specify one.
+    pub fn thickness(&self, thickness: Option<f64>) -> f64 {
+        thickness.unwrap_or(self.theme.thickness * self.pixel.unwrap_or(1.0))
+    }
+
+    /// Width (pixels) of a line of the given thickness (world units), as seen by the camera.
+    /// 
+    /// Lines are always at least one pixel thick, so that thin lines don't vanish
+    /// at low resolutions.
+    pub fn line_width(&self, thickness: f64) -> f64 {
+        (thickness * self.camera.zoom * self.scale).max(1.0)
+    }
+
+    /// Add a pixel to this frame with a given strength.
+    pub fn add_pixel(&mut self, x: u32, y: u32, color: Rgb<u8>, strength: f64) {
+        add_pixel(&mut self.image, x, y, self.compositing.decode(color), strength, self.compositing, self.blend);
+    }
+
+    /// Stroke a path of points (world coordinates) with a given stroke style and color
+    /// (or the theme's foreground color).
+    /// 
+    /// The whole stroke is rasterized at once, so every pixel is blended only once,
+    /// even where the stroke overlaps itself.  If the stroke has a paint, it is used
+    /// instead of the color, and `params` gives the position along the stroke (between
+    /// 0 and 1) of each point.  Without `params`, points are placed by their distance
+    /// along the stroke.
+    pub fn stroke(&mut self, path: &[Vector], params: &[f64], closed: bool, stroke: &Stroke, color: Option<Rgb<u8>>) {
+        // Convert points to pixels
+        let points: Vec<Vector> = path.iter().map(|point| self.to_pixels(*point)).collect();
+
+        // Outline of this stroke, or of each of its dashes
+        let width = self.line_width(self.thickness(stroke.thickness));
+        let outline = if stroke.dash.is_empty() {
+            stroke.outline(&points, closed, width)
+        } else {
+            stroke.dashes(&points, closed, self.camera.zoom * self.scale).iter()
+                .flat_map(|dash| stroke.outline(dash, false, width))
+                .collect()
+        };
+
+        let Some (mask) = rasterize(&outline, self.image.width(), self.image.height(), self.antialias) else {
+            return;
+        };
+
+        match &stroke.paint {
+            Some (paint) if paint.is_along() => {
+                let along = AlongStroke::new(&points, params, width);
+                self.paint_mask(&mask, paint, Some (&along), 1.0);
+            },
+            Some (paint) => self.paint_mask(&mask, paint, None, 1.0),
+            None => self.fill_mask(&mask, self.color(color)),
+        }
+    }
+
+    /// Fill a set of polygons (world coordinates) with a paint and opacity.
+    /// 
+    /// Polygons are filled with the nonzero winding rule, so opposite orientations make holes.
+    pub fn fill(&mut self, polygons: &[Vec<Vector>], paint: &Paint, opacity: f64) {
+        let polygons: Vec<Vec<Vector>> = polygons.iter()
+            .map(|polygon| polygon.iter().map(|point| self.to_pixels(*point)).collect())
+            .collect();
+
+        if let Some (mask) = rasterize(&polygons, self.image.width(), self.image.height(), self.antialias) {
+            self.paint_mask(&mask, paint, None, opacity);
+        }
+    }
+
+    /// Paint a mask on this frame with a paint and opacity.
+    /// 
+    /// Paints that change along a stroke need the positions `along` that stroke.
+    pub fn paint_mask(&mut self, mask: &Mask, paint: &Paint, along: Option<&AlongStroke>, opacity: f64) {
+        for (x, y, coverage) in mask.pixels() {
+            // Center of this pixel
+            let pixel = Vector::new(x as f64 + 0.5, y as f64 + 0.5);
+            let t = along.map_or(0.0, |along| along.at(pixel));
+
+            self.add_pixel(x, y, paint.at(self.to_world(pixel), t), coverage as f64 * opacity);
+        }
+    }
+
+    /// Fill a mask on this frame with a color.
+    pub fn fill_mask(&mut self, mask: &Mask, color: Rgb<u8>) {
+        for (x, y, coverage) in mask.pixels() {
+            self.add_pixel(x, y, color, coverage as f64);
+        }
+    }
+
+    /// Downsample this frame by averaging each square block of `factor` by `factor` pixels,
+    /// converting it into an image that can be saved.
+    /// 
+    /// Blocks are averaged in linear light, unless compositing the legacy way.
+    pub fn downsample(&self, factor: u32) -> RgbImage {
+        let factor = factor.max(1);
+
+        // Output size
+        let width = self.image.width() / factor;
+        let height = self.image.height() / factor;
+        let count = factor * factor;
+
+        RgbImage::from_fn(width, height, |x, y| {
+            // Pixels in this block
+            let block = (0..factor).flat_map(|i| (0..factor).map(move |j| (i, j)))
+                .map(|(i, j)| *self.image.get_pixel(x * factor + i, y * factor + j));
+
+            match self.compositing {
+                Compositing::Linear => {
+                    let mut sum = [0.0f32; 4];
+                    for pixel in block {
+                        for c in 0..4 {
+                            sum[c] += pixel[c];
+                        }
+                    }
+
+                    self.compositing.encode(Rgba (sum.map(|s| s / count as f32)))
+                },
+                Compositing::Legacy => {
+                    let mut sum = [0u32; 3];
+                    for pixel in block {
+                        let pixel = self.compositing.encode(pixel);
+                        for c in 0..3 {
+                            sum[c] += pixel[c] as u32;
+                        }
+                    }
+
+                    // Average, rounding to the nearest value
+                    Rgb (sum.map(|s| ((s + count / 2) / count) as u8))
+                },
+            }
+        })
+    }
+}
@@ -1,41 +1,136 @@
-//! Interpolation between two Bezier curves.
+//! Interpolation between two shapes.
 
-use image::{
-    Rgb,
-    RgbImage,
-};
+use image::Rgb;
 
 use crate::{
-    add_pixel,
+    bounds,
+    signed_area,
     Animate,
     Artist,
-    Bresenham,
-    Brush,
+    Bezier,
+    ColorSpace,
+    Frame,
+    Paint,
+    Parametric,
     Shape,
-    STEP,
+    Stroke,
     Vector,
 };
 
+/// Outlines whose ends are closer than this (world units) are closed.
+const CLOSED: f64 = 1E-6;
+
+/// Outlines smaller than this (world units) have vanished, and are not drawn.
+const VANISHED: f64 = 1E-9;
+
+/// Fewest control points of each curve being interpolated (so that corners can become curves).
+const MIN_POINTS: usize = 4;
+
+#[derive(Clone)]
+/// Color, stroke style, and fill of an object being interpolated.
+pub struct Style {
+    /// Color, or `None` for the theme's foreground color.
+    pub color: Option<Rgb<u8>>,
+
+    /// Stroke style.
+    pub stroke: Stroke,
+
+    /// Paint filling the inside, if any.
+    pub fill: Option<Paint>,
+}
+
+impl Style {
+    /// Blend this style with another, giving the stroke style partway between them and
+    /// the fill (with its opacity), if any.
+    pub fn blend(&self, other: &Style, frame: &Frame, progress: f64, space: ColorSpace) -> (Stroke, Option<(Paint, f64)>) {
+        // Interpolate paints, treating solid colors as solid paints
+        let paint_one = self.stroke.paint.clone().unwrap_or_else(|| Paint::solid(frame.color(self.color)));
+        let paint_two = other.stroke.paint.clone().unwrap_or_else(|| Paint::solid(frame.color(other.color)));
+        let paint = paint_one.lerp(&paint_two, progress, space);
+
+        let stroke = Stroke {
+            thickness: Some (
+                frame.thickness(self.stroke.thickness) * (1.0 - progress)
+                    + frame.thickness(other.stroke.thickness) * progress
+            ),
+            paint: Some (paint),
+            ..self.stroke.clone()
+        };
+
+        // Interpolate fills, fading in or out if only one is filled
+        let fill = match (&self.fill, &other.fill) {
+            (Some (one), Some (two)) => Some ((one.lerp(two, progress, space), 1.0)),
+            (Some (one), None) => Some ((one.clone(), 1.0 - progress)),
+            (None, Some (two)) => Some ((two.clone(), progress)),
+            (None, None) => None,
+        };
+
+        (stroke, fill)
+    }
+}
+
+impl From<&Shape> for Style {
+    fn from(shape: &Shape) -> Self {
+        Self {
+            color: shape.color,
+            stroke: shape.stroke.clone(),
+            fill: shape.fill.clone(),
+        }
+    }
+}
+
+impl From<&Parametric> for Style {
+    fn from(curve: &Parametric) -> Self {
+        Self {
+            color: curve.color,
+            stroke: curve.stroke.clone(),
+            fill: curve.fill.clone(),
+        }
+    }
+}
+
 #[derive(Clone)]
 /// An interpolation animation, where one shape smoothly becomes another.
+///
+/// The outlines of the two shapes are paired, largest first, and an outline without a
+/// partner grows from (or shrinks to) its center.  Curves of each pair are split until
+/// both outlines have the same number of curves, and closed outlines are turned to run
+/// the same way around, starting where their control points travel the least.
 pub struct Interpolate {
-    one: Shape,
-    two: Shape,
+    one: Style,
+    two: Style,
+
+    /// Pairs of matching outlines, with the same number of curves and control points.
+    pairs: Vec<(Vec<Bezier>, Vec<Bezier>)>,
+
+    /// Color space in which colors are blended.
+    space: ColorSpace,
 }
 
 impl Interpolate {
-    /// Construct a new interpolation.
-    pub fn new(one: Shape, two: Shape) -> Self {
+    /// Construct a new interpolation, blending colors in a given color space.
+    pub fn new(one: Shape, two: Shape, space: ColorSpace) -> Self {
         Self {
-            one,
-            two,
+            one: Style::from(&one),
+            two: Style::from(&two),
+            pairs: pairs(&one, &two),
+            space,
         }
     }
 }
 
 impl Animate for Interpolate {
     fn play(&self, progress: f64) -> Box<dyn Artist> {
-        Box::new(InterpolatedCurve::new(self.one.clone(), self.two.clone(), progress))
+        let curves = self.pairs.iter()
+            .map(|(one, two)| one.iter().zip(two).map(|(one, two)| one.lerp(two, progress)).collect::<Vec<Bezier>>())
+            .filter(|outline| {
+                let (min, max) = bounds(outline.iter().flat_map(|curve| curve.placed_points()));
+                (max - min).norm() > VANISHED
+            })
+            .flatten()
+            .collect();
+
+        Box::new(InterpolatedCurve::new(self.one.clone(), self.two.clone(), curves, progress, self.space))
     }
 
     fn clone_box(&self) -> Box<dyn Animate> {
@@ -45,80 +140,179 @@ impl Animate for Interpolate {
 
 /// A linear interpolation between two shapes.
 pub struct InterpolatedCurve {
-    one: Shape,
-    two: Shape,
+    one: Style,
+    two: Style,
+
+    /// Curves partway between those of the two shapes (world coordinates).
+    curves: Vec<Bezier>,
+
     progress: f64,
+    space: ColorSpace,
 }
 
 impl InterpolatedCurve {
     /// Construct a new interpolated curve.
-    pub fn new(one: Shape, two: Shape, progress: f64) -> Self {
+    pub fn new(one: Style, two: Style, curves: Vec<Bezier>, progress: f64, space: ColorSpace) -> Self {
         Self {
             one,
             two,
+            curves,
             progress,
+            space,
         }
     }
 }
 
 impl Artist for InterpolatedCurve {
-    fn draw(&self, location: Vector, image: &mut RgbImage) {
-        // Build collection of points to interpolate between
-        let mut t = 0.0f64;
-        let mut points = Vec::new();
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        let (stroke, fill) = self.one.blend(&self.two, frame, self.progress, self.space);
+
+        // Shape partway between the two shapes, in this style
+        let shape = Shape {
+            curves: self.curves.iter()
+                .map(|curve| {
+                    let mut curve = curve.clone();
+                    curve.color = None;
+                    curve.stroke = stroke.clone();
+                    curve
+                })
+                .collect(),
+            stroke,
+            origin: Vector::zero(),
+            color: None,
+            fill: None,
+            arrowheads: None,
+        };
 
-        while t <= 1.0 {
-            // Fix floating-point errors
-            let t_fixed = t.clamp(0.0, 1.0 - STEP);
+        if let Some ((fill, opacity)) = fill {
+            frame.fill(&shape.contours(location), &fill, opacity);
+        }
 
-            // Calculate offset from location
-            let trace1 = self.one.trace(t_fixed) + location;
-            let trace2 = self.two.trace(t_fixed) + location;
+        for path in shape.paths(location, |_| Some ((0.0, 1.0))) {
+            path.draw(frame);
+        }
+    }
+}
 
-            // Compute weighted average
-            let trace = trace1 * (1.0 - self.progress) + trace2 * self.progress;
+/// Pair the outlines of two shapes, giving both outlines of each pair the same number of
+/// curves with the same number of control points.
+fn pairs(one: &Shape, two: &Shape) -> Vec<(Vec<Bezier>, Vec<Bezier>)> {
+    let mut ones = one.outlines();
+    let mut twos = two.outlines();
 
-            // Save this point
-            points.push(location + trace);
+    // Largest outlines first
+    let size = |outline: &Vec<Bezier>| signed_area(&sampled(outline)).abs();
+    ones.sort_by(|a, b| size(b).total_cmp(&size(a)));
+    twos.sort_by(|a, b| size(b).total_cmp(&size(a)));
 
-            // Step along the curve
-            t += STEP;
-        }
+    // Outlines without a partner grow from (or shrink to) a point
+    while ones.len() < twos.len() {
+        ones.push(collapsed(&twos[ones.len()]));
+    }
+    while twos.len() < ones.len() {
+        twos.push(collapsed(&ones[twos.len()]));
+    }
 
-        // Interpolate colors
-        // TODO this is a sloppy interpolation, can it be better?
-        let (r, g, b): (f64, f64, f64) = (
-            (self.one.color.0[0] as f64) * (1.0 - self.progress) + (self.two.color.0[0] as f64) * self.progress,
-            (self.one.color.0[1] as f64) * (1.0 - self.progress) + (self.two.color.0[1] as f64) * self.progress,
-            (self.one.color.0[2] as f64) * (1.0 - self.progress) + (self.two.color.0[2] as f64) * self.progress,
-        );
-        let color = Rgb ([r as u8, g as u8, b as u8]);
-
-        // Thickness of this interpolation
-        let thickness = (self.one.thickness as f64) * (1.0 - self.progress) + (self.two.thickness as f64) * self.progress;
-
-        // Brush to draw with
-        let brush = Brush::new(thickness.round() as i32);
-
-        // Interpolation (Bresenham's line algorithm)
-        for i in 0..(points.len() - 1) {
-            // Two points to draw between
-            let this_point = points[i];
-            let next_point = points[i + 1];
-
-            // Convert points to integers
-            let (x0, y0) = this_point.to_pixels(image.width(), image.height());
-            let (x1, y1) = next_point.to_pixels(image.width(), image.height());
-
-            // Construct Bresenham line
-            let line = Bresenham::new(x0, y0, x1, y1).points;
-
-            // Draw first point
-            for (x, y) in line {
-                for (i, j, strength) in &brush.points {
-                    add_pixel(image, (x as i32 + i) as u32, (y as i32 + j) as u32, color, *strength);
-                }
+    // Every curve has as many control points as the largest curve
+    let count = ones.iter().chain(&twos).flatten()
+        .map(|curve| curve.placed_points().len())
+        .fold(MIN_POINTS, usize::max);
+
+    ones.into_iter().zip(twos)
+        .map(|(one, two)| {
+            let curves = one.len().max(two.len());
+            align(elevated(split(one, curves), count), elevated(split(two, curves), count))
+        })
+        .collect()
+}
+
+/// Sample an outline into points.
+fn sampled(outline: &[Bezier]) -> Vec<Vector> {
+    outline.iter().flat_map(|curve| curve.sample(0.0, 1.0)).collect()
+}
+
+/// Does an outline end where it begins?
+fn is_closed(outline: &[Bezier]) -> bool {
+    match (outline.first(), outline.last()) {
+        (Some (first), Some (last)) => (first.trace(0.0) - last.trace(1.0)).norm() < CLOSED,
+        _ => false,
+    }
+}
+
+/// Construct an outline with the same curves as another, collapsed to its center.
+fn collapsed(outline: &[Bezier]) -> Vec<Bezier> {
+    let center = outline.iter().map(|curve| curve.trace(0.0)).fold(Vector::zero(), |sum, point| sum + point)
+        * (1.0 / outline.len() as f64);
+
+    outline.iter()
+        .map(|curve| Bezier::new(vec![center; curve.placed_points().len()], Vector::zero(), curve.color, curve.stroke.thickness))
+        .collect()
+}
+
+/// Split the longest curves of an outline in half until it has a given number of curves.
+fn split(mut outline: Vec<Bezier>, count: usize) -> Vec<Bezier> {
+    while outline.len() < count {
+        let longest = (0..outline.len())
+            .max_by(|a, b| outline[*a].length().total_cmp(&outline[*b].length()))
+            .unwrap_or(0);
+
+        let (first, second) = outline[longest].split(0.5);
+        outline.splice(longest..=longest, [first, second]);
+    }
+
+    outline
+}
+
+/// Elevate the curves of an outline until each has a given number of control points.
+fn elevated(outline: Vec<Bezier>, count: usize) -> Vec<Bezier> {
+    outline.into_iter()
+        .map(|mut curve| {
+            while curve.placed_points().len() < count {
+                curve = curve.elevate();
             }
+            curve
+        })
+        .collect()
+}
+
+/// Construct an outline traced backwards.
+fn reversed(outline: &[Bezier]) -> Vec<Bezier> {
+    outline.iter().rev().map(|curve| curve.reversed()).collect()
+}
+
+/// Measure how far the control points of one outline travel to become another (the sum of
+/// squared distances).
+fn travel(one: &[Bezier], two: &[Bezier]) -> f64 {
+    one.iter().zip(two)
+        .flat_map(|(one, two)| one.placed_points().into_iter().zip(two.placed_points()))
+        .map(|(a, b)| (b - a).dot(b - a))
+        .sum()
+}
+
+/// Turn the second of two outlines (with the same number of curves) to match the first as
+/// closely as possible.
+///
+/// Closed outlines are made to run the same way around, and to start where the control
+/// points travel the least.  Open outlines are reversed, if that makes them travel less.
+fn align(one: Vec<Bezier>, mut two: Vec<Bezier>) -> (Vec<Bezier>, Vec<Bezier>) {
+    if is_closed(&one) && is_closed(&two) {
+        if signed_area(&sampled(&one)) * signed_area(&sampled(&two)) < 0.0 {
+            two = reversed(&two);
         }
+
+        let start = (0..two.len())
+            .map(|k| {
+                let mut rotated = two.clone();
+                rotated.rotate_left(k);
+
+                (k, travel(&one, &rotated))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(0, |(k, _)| k);
+        two.rotate_left(start);
+    } else if travel(&one, &reversed(&two)) < travel(&one, &two) {
+        two = reversed(&two);
     }
-}
\ No newline at end of file
+
+    (one, two)
+}
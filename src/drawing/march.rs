@@ -0,0 +1,40 @@
+//! Move the dashes of a shape.
+
+use crate::{
+    Artist,
+    Animate,
+    Shape,
+};
+
+#[derive(Clone)]
+/// An animation that moves the dashes of a shape along its outline ("marching ants").
+pub struct March {
+    /// Shape whose dashes are moved.
+    shape: Shape,
+
+    /// Distance (world units) the dashes move.
+    distance: f64,
+}
+
+impl March {
+    /// Construct a new marching animation.
+    pub fn new(shape: Shape, distance: f64) -> Self {
+        Self {
+            shape,
+            distance,
+        }
+    }
+}
+
+impl Animate for March {
+    fn play(&self, progress: f64) -> Box<dyn Artist> {
+        let mut shape = self.shape.clone();
+        shape.set_dash_offset(self.shape.stroke.dash_offset - self.distance * progress);
+
+        Box::new(shape)
+    }
+
+    fn clone_box(&self) -> Box<dyn Animate> {
+        Box::new(self.clone())
+    }
+}
@@ -2,9 +2,16 @@
 
 mod animation;
 mod artist;
-mod bresenham;
-mod brush;
+mod blend;
+mod clip;
+mod color;
+mod effect;
+mod frame;
 mod interpolate;
+mod march;
+mod paint;
+mod raster;
+mod stroke;
 mod trace;
 
 pub use animation::{
@@ -12,9 +19,42 @@ pub use animation::{
     Animation,
 };
 pub use artist::Artist;
-pub use bresenham::Bresenham;
-pub use brush::Brush;
-pub use interpolate::Interpolate;
+pub use blend::{
+    BlendMode,
+    Compositing,
+};
+pub use clip::{
+    Clip,
+    ClipRegion,
+    WipeDirection,
+};
+pub use color::{
+    byte_to_linear,
+    linear_to_byte,
+    Color,
+    ColorSpace,
+};
+pub use effect::Effect;
+pub use frame::Frame;
+pub use interpolate::{
+    Interpolate,
+    Style,
+};
+pub use march::March;
+pub use paint::{
+    AlongStroke,
+    Paint,
+};
+pub use raster::{
+    rasterize,
+    Mask,
+};
+pub use stroke::{
+    signed_area,
+    LineCap,
+    LineJoin,
+    Stroke,
+};
 pub use trace::{
     Trace,
     TracedShape,
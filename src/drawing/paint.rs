@@ -0,0 +1,299 @@
+//! Paints, which color strokes and fills.
+
+use std::collections::HashMap;
+
+use image::Rgb;
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    Color,
+    ColorSpace,
+    Vector,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// How the position within a gradient is found.
+pub enum Gradient {
+    /// A single color everywhere.
+    Solid,
+
+    /// Colors change along the line from `start` to `end` (world coordinates).
+    Linear {
+        start: Vector,
+        end: Vector,
+    },
+
+    /// Colors change with distance from `center` (world coordinates), out to `radius`.
+    Radial {
+        center: Vector,
+        radius: f64,
+    },
+
+    /// Colors change along a stroke, from its start (`t = 0`) to its end (`t = 1`).
+    Along,
+}
+
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+/// A paint, which colors strokes and fills.
+///
+/// A paint is either a solid color or a gradient between a number of color stops.
+pub struct Paint {
+    /// How the position within the gradient is found.
+    pub gradient: Gradient,
+
+    /// Color stops, as positions (between 0 and 1) and colors, in order of position.
+    pub stops: Vec<(f64, Rgb<u8>)>,
+}
+
+#[pymethods]
+impl Paint {
+    #[new]
+    /// Construct a new solid paint.
+    pub fn new(color: Color) -> Self {
+        Self::solid(color.rgb())
+    }
+
+    #[staticmethod]
+    #[pyo3(signature=(start, end, colors, stops=None))]
+    /// Construct a linear gradient from `start` to `end` (world coordinates).
+    ///
+    /// Colors are spaced evenly unless their positions (between 0 and 1) are given.
+    pub fn linear(start: Vector, end: Vector, colors: Vec<Color>, stops: Option<Vec<f64>>) -> PyResult<Self> {
+        if (end - start).norm() == 0.0 {
+            return Err(PyValueError::new_err("linear gradient must have distinct start and end"));
+        }
+
+        Self::gradient(Gradient::Linear { start, end }, colors, stops)
+    }
+
+    #[staticmethod]
+    #[pyo3(signature=(center, radius, colors, stops=None))]
+    /// Construct a radial gradient around `center` (world coordinates), out to `radius`.
+    ///
+    /// Colors are spaced evenly unless their positions (between 0 and 1) are given.
+    pub fn radial(center: Vector, radius: f64, colors: Vec<Color>, stops: Option<Vec<f64>>) -> PyResult<Self> {
+        if radius <= 0.0 {
+            return Err(PyValueError::new_err("radial gradient must have a positive radius"));
+        }
+
+        Self::gradient(Gradient::Radial { center, radius }, colors, stops)
+    }
+
+    #[staticmethod]
+    #[pyo3(signature=(colors, stops=None))]
+    /// Construct a gradient along a stroke, from its start to its end.
+    ///
+    /// Colors are spaced evenly unless their positions (between 0 and 1) are given.
+    pub fn along(colors: Vec<Color>, stops: Option<Vec<f64>>) -> PyResult<Self> {
+        Self::gradient(Gradient::Along, colors, stops)
+    }
+}
+
+impl Paint {
+    /// Construct a new solid paint from an RGB color.
+    pub fn solid(color: Rgb<u8>) -> Self {
+        Self {
+            gradient: Gradient::Solid,
+            stops: vec![(0.0, color)],
+        }
+    }
+
+    /// Construct a gradient from colors and optional stop positions.
+    fn gradient(gradient: Gradient, colors: Vec<Color>, stops: Option<Vec<f64>>) -> PyResult<Self> {
+        if colors.is_empty() {
+            return Err(PyValueError::new_err("gradient must have at least one color"));
+        }
+
+        // Space colors evenly by default
+        let n = colors.len();
+        let stops = stops.unwrap_or_else(|| {
+            (0..n).map(|i| if n > 1 { i as f64 / (n - 1) as f64 } else { 0.0 }).collect()
+        });
+
+        if stops.len() != n {
+            return Err(PyValueError::new_err("gradient must have one stop per color"));
+        }
+
+        if stops.iter().any(|stop| !(0.0..=1.0).contains(stop)) || stops.windows(2).any(|pair| pair[1] < pair[0]) {
+            return Err(PyValueError::new_err("gradient stops must increase from 0 to 1"));
+        }
+
+        Ok(Self {
+            gradient,
+            stops: stops.into_iter().zip(colors.iter().map(Color::rgb)).collect(),
+        })
+    }
+
+    /// Does this paint change along a stroke?
+    pub fn is_along(&self) -> bool {
+        self.gradient == Gradient::Along
+    }
+
+    /// Color of this paint at a point (world coordinates), `t` of the way along a stroke.
+    pub fn at(&self, point: Vector, t: f64) -> Rgb<u8> {
+        let position = match self.gradient {
+            Gradient::Solid => 0.0,
+            Gradient::Linear { start, end } => (point - start).dot(end - start) / (end - start).dot(end - start),
+            Gradient::Radial { center, radius } => (point - center).norm() / radius,
+            Gradient::Along => t,
+        };
+
+        self.color(position)
+    }
+
+    /// Color of this paint at a position within its gradient.
+    pub fn color(&self, position: f64) -> Rgb<u8> {
+        let position = position.clamp(0.0, 1.0);
+
+        // First stop at or after this position
+        let i = self.stops.partition_point(|(stop, _)| *stop < position);
+
+        if i == 0 {
+            return self.stops[0].1;
+        }
+        if i == self.stops.len() {
+            return self.stops[i - 1].1;
+        }
+
+        // Blend neighboring stops
+        let (a, color_a) = self.stops[i - 1];
+        let (b, color_b) = self.stops[i];
+        let p = if b > a { (position - a) / (b - a) } else { 1.0 };
+
+        ColorSpace::Srgb.lerp(color_a, color_b, p)
+    }
+
+    /// Interpolate between this paint and another, blending colors in a given color space.
+    ///
+    /// Paints of the same kind with the same number of stops are blended smoothly.
+    /// Otherwise, the paint switches halfway through.
+    pub fn lerp(&self, other: &Paint, progress: f64, space: ColorSpace) -> Paint {
+        let mix = |a: f64, b: f64| a * (1.0 - progress) + b * progress;
+
+        let gradient = match (self.gradient, other.gradient) {
+            (Gradient::Solid, Gradient::Solid) => Gradient::Solid,
+            (Gradient::Along, Gradient::Along) => Gradient::Along,
+            (Gradient::Linear { start: s1, end: e1 }, Gradient::Linear { start: s2, end: e2 }) => Gradient::Linear {
+                start: s1 * (1.0 - progress) + s2 * progress,
+                end: e1 * (1.0 - progress) + e2 * progress,
+            },
+            (Gradient::Radial { center: c1, radius: r1 }, Gradient::Radial { center: c2, radius: r2 }) => Gradient::Radial {
+                center: c1 * (1.0 - progress) + c2 * progress,
+                radius: mix(r1, r2),
+            },
+            _ => return if progress < 0.5 { self.clone() } else { other.clone() },
+        };
+
+        if self.stops.len() != other.stops.len() {
+            return if progress < 0.5 { self.clone() } else { other.clone() };
+        }
+
+        Paint {
+            gradient,
+            stops: self.stops.iter().zip(&other.stops)
+                .map(|((a, color_a), (b, color_b))| (mix(*a, *b), space.lerp(*color_a, *color_b, progress)))
+                .collect(),
+        }
+    }
+}
+
+/// Positions along a stroke, used to find how far along the stroke each pixel lies.
+///
+/// Segments of the stroke are sorted into a grid of square cells, so that only nearby
+/// segments are searched for each pixel.
+pub struct AlongStroke {
+    /// Points along the stroke (pixel coordinates).
+    points: Vec<Vector>,
+
+    /// Position along the stroke (between 0 and 1) of each point.
+    params: Vec<f64>,
+
+    /// Size of each grid cell (pixels).
+    cell: f64,
+
+    /// Indices of the segments that may cover each grid cell.
+    grid: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl AlongStroke {
+    /// Construct positions along a stroke of a given width (pixels).
+    ///
+    /// If no positions are given, points are placed by their distance along the stroke.
+    pub fn new(points: &[Vector], params: &[f64], width: f64) -> Self {
+        let params = if params.len() == points.len() {
+            params.to_vec()
+        } else {
+            // Distance along the stroke
+            let mut distances = vec![0.0];
+            for pair in points.windows(2) {
+                distances.push(distances[distances.len() - 1] + (pair[1] - pair[0]).norm());
+            }
+
+            let total = distances[distances.len() - 1];
+            distances.into_iter().map(|d| if total > 0.0 { d / total } else { 0.0 }).collect()
+        };
+
+        // Pixels may lie up to a stroke width from their segment (at square caps)
+        let cell = width.max(4.0);
+        let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+
+        for (i, pair) in points.windows(2).enumerate() {
+            let (a, b) = (pair[0], pair[1]);
+            let x0 = ((a.x.min(b.x) - width) / cell).floor() as i64;
+            let x1 = ((a.x.max(b.x) + width) / cell).floor() as i64;
+            let y0 = ((a.y.min(b.y) - width) / cell).floor() as i64;
+            let y1 = ((a.y.max(b.y) + width) / cell).floor() as i64;
+
+            for x in x0..=x1 {
+                for y in y0..=y1 {
+                    grid.entry((x, y)).or_default().push(i);
+                }
+            }
+        }
+
+        Self {
+            points: points.to_vec(),
+            params,
+            cell,
+            grid,
+        }
+    }
+
+    /// Position along the stroke (between 0 and 1) of the point on the stroke nearest to a pixel.
+    pub fn at(&self, pixel: Vector) -> f64 {
+        if self.points.len() < 2 {
+            return self.params.first().copied().unwrap_or(0.0);
+        }
+
+        // Nearby segments, or every segment if none are nearby
+        let key = ((pixel.x / self.cell).floor() as i64, (pixel.y / self.cell).floor() as i64);
+        let all: Vec<usize>;
+        let segments = match self.grid.get(&key) {
+            Some (segments) => segments,
+            None => {
+                all = (0..self.points.len() - 1).collect();
+                &all
+            },
+        };
+
+        // Nearest point on the nearest segment
+        let mut best = (f64::INFINITY, 0.0);
+        for &i in segments {
+            let (a, b) = (self.points[i], self.points[i + 1]);
+            let length = (b - a).dot(b - a);
+            let u = if length > 0.0 { ((pixel - a).dot(b - a) / length).clamp(0.0, 1.0) } else { 0.0 };
+            let distance = (pixel - (a + (b - a) * u)).norm();
+
+            if distance < best.0 {
+                best = (distance, self.params[i] * (1.0 - u) + self.params[i + 1] * u);
+            }
+        }
+
+        best.1
+    }
+}
@@ -0,0 +1,224 @@
+//! Polygon rasterization with anti-aliasing.
+
+use crate::Vector;
+
+/// Number of sub-scanlines sampled per row of pixels, when anti-aliasing.
+const SUBSAMPLES: usize = 4;
+
+/// Coverage of a region of a frame, with values between 0 and 1.
+pub struct Mask {
+    /// Leftmost pixel column of this mask.
+    pub x: u32,
+
+    /// Topmost pixel row of this mask.
+    pub y: u32,
+
+    /// Width of this mask (pixels).
+    pub width: u32,
+
+    /// Height of this mask (pixels).
+    pub height: u32,
+
+    /// Coverage of each pixel, row by row.
+    pub coverage: Vec<f32>,
+}
+
+impl Mask {
+    /// Coverage of a pixel in frame coordinates (zero outside this mask).
+    pub fn get(&self, x: u32, y: u32) -> f32 {
+        if x < self.x || y < self.y || x >= self.x + self.width || y >= self.y + self.height {
+            return 0.0;
+        }
+
+        self.coverage[((y - self.y) * self.width + (x - self.x)) as usize]
+    }
+
+    /// Iterate over every pixel with nonzero coverage, in frame coordinates.
+    pub fn pixels(&self) -> impl Iterator<Item = (u32, u32, f32)> + '_ {
+        self.coverage.iter().enumerate().filter(|(_, c)| **c > 0.0).map(|(i, c)| {
+            let i = i as u32;
+            (self.x + i % self.width, self.y + i / self.width, *c)
+        })
+    }
+}
+
+/// A polygon edge that is not horizontal.
+struct Edge {
+    /// Top of the edge.
+    y_min: f64,
+
+    /// Bottom of the edge.
+    y_max: f64,
+
+    /// Horizontal position at the top of the edge.
+    x_top: f64,
+
+    /// Change in horizontal position per unit of vertical position.
+    slope: f64,
+
+    /// Winding direction (+1 or -1).
+    winding: i32,
+}
+
+/// Rasterize a set of polygons (pixel coordinates) with the nonzero winding rule.
+/// 
+/// Pixel `(x, y)` covers the square from `(x, y)` to `(x + 1, y + 1)`.  Overlapping polygons
+/// with the same orientation are filled once, without overlapping.  Returns `None` if the
+/// polygons don't cover any part of a `width` by `height` frame.
+pub fn rasterize(polygons: &[Vec<Vector>], width: u32, height: u32, antialias: bool) -> Option<Mask> {
+    // Collect edges and bounding box
+    let mut edges = Vec::new();
+    let (mut x_min, mut y_min) = (f64::INFINITY, f64::INFINITY);
+    let (mut x_max, mut y_max) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+    for polygon in polygons {
+        for i in 0..polygon.len() {
+            let a = polygon[i];
+            let b = polygon[(i + 1) % polygon.len()];
+
+            // Ignore edges with invalid points
+            if !(a.x.is_finite() && a.y.is_finite() && b.x.is_finite() && b.y.is_finite()) {
+                continue;
+            }
+
+            x_min = x_min.min(a.x);
+            x_max = x_max.max(a.x);
+            y_min = y_min.min(a.y);
+            y_max = y_max.max(a.y);
+
+            // Horizontal edges never cross a scanline
+            if a.y == b.y {
+                continue;
+            }
+
+            let (top, bottom, winding) = if a.y < b.y { (a, b, 1) } else { (b, a, -1) };
+
+            edges.push(Edge {
+                y_min: top.y,
+                y_max: bottom.y,
+                x_top: top.x,
+                slope: (bottom.x - top.x) / (bottom.y - top.y),
+                winding,
+            });
+        }
+    }
+
+    // Constrain bounding box to the frame
+    let x0 = x_min.floor().max(0.0);
+    let y0 = y_min.floor().max(0.0);
+    let x1 = x_max.ceil().min(width as f64);
+    let y1 = y_max.ceil().min(height as f64);
+
+    if edges.is_empty() || x0 >= x1 || y0 >= y1 {
+        return None;
+    }
+
+    let mut mask = Mask {
+        x: x0 as u32,
+        y: y0 as u32,
+        width: (x1 - x0) as u32,
+        height: (y1 - y0) as u32,
+        coverage: vec![0.0; ((x1 - x0) * (y1 - y0)) as usize],
+    };
+
+    // Sort edges from top to bottom
+    edges.sort_by(|a, b| a.y_min.total_cmp(&b.y_min));
+
+    // Sampled scanlines within each row, with their weights
+    let (samples, weight) = if antialias {
+        (SUBSAMPLES, 1.0 / SUBSAMPLES as f64)
+    } else {
+        (1, 1.0)
+    };
+
+    // Edges crossing the current scanline
+    let mut active: Vec<&Edge> = Vec::new();
+    let mut next = 0;
+    let mut crossings: Vec<(f64, i32)> = Vec::new();
+
+    for row in 0..mask.height {
+        // Coverage of this row
+        let start = (row * mask.width) as usize;
+        let coverage = &mut mask.coverage[start..start + mask.width as usize];
+
+        for s in 0..samples {
+            // Vertical position of this scanline
+            let y = y0 + row as f64 + (s as f64 + 0.5) / samples as f64;
+
+            // Update active edges
+            while next < edges.len() && edges[next].y_min <= y {
+                active.push(&edges[next]);
+                next += 1;
+            }
+            active.retain(|edge| edge.y_max > y);
+
+            // Find crossings, from left to right
+            crossings.clear();
+            for edge in &active {
+                crossings.push((edge.x_top + (y - edge.y_min) * edge.slope, edge.winding));
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            // Fill spans with nonzero winding
+            let mut winding = 0;
+            let mut span_start = 0.0;
+            for (x, w) in &crossings {
+                if winding == 0 {
+                    span_start = *x;
+                }
+
+                winding += w;
+
+                if winding == 0 {
+                    add_span(coverage, span_start - x0, x - x0, weight, antialias);
+                }
+            }
+        }
+    }
+
+    // Floating-point errors may add up to slightly more than full coverage
+    for c in mask.coverage.iter_mut() {
+        *c = c.min(1.0);
+    }
+
+    Some (mask)
+}
+
+/// Add a horizontal span (from `a` to `b`, relative to the start of the row) to a row of coverage.
+fn add_span(coverage: &mut [f32], a: f64, b: f64, weight: f64, antialias: bool) {
+    let len = coverage.len() as f64;
+
+    if antialias {
+        // Constrain span to this row
+        let (a, b) = (a.clamp(0.0, len), b.clamp(0.0, len));
+        if a >= b {
+            return;
+        }
+
+        let (ia, ib) = (a.floor() as usize, b.floor() as usize);
+
+        if ia == ib {
+            // Span lies within a single pixel
+            coverage[ia] += ((b - a) * weight) as f32;
+        } else {
+            // Partially covered pixels at either end
+            coverage[ia] += ((ia as f64 + 1.0 - a) * weight) as f32;
+            if ib < coverage.len() {
+                coverage[ib] += ((b - ib as f64) * weight) as f32;
+            }
+
+            // Fully covered pixels in between
+            for c in &mut coverage[ia + 1..ib] {
+                *c += weight as f32;
+            }
+        }
+    } else {
+        // Pixels whose centers lie in the span
+        let ia = (a - 0.5).ceil().clamp(0.0, len) as usize;
+        let ib = (b - 0.5).ceil().clamp(0.0, len) as usize;
+
+        for c in &mut coverage[ia..ib.max(ia)] {
+            *c += weight as f32;
+        }
+    }
+}
\ No newline at end of file
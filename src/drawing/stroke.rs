@@ -0,0 +1,448 @@
+//! Stroking paths with caps and joins.
+
+use std::f64::consts::PI;
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    Paint,
+    Vector,
+};
+
+/// Miter joins longer than this multiple of the stroke thickness are drawn as bevel joins.
+pub const MITER_LIMIT: f64 = 4.0;
+
+/// Maximum distance (pixels) between a round cap or join and its polygonal approximation.
+const ROUND_TOLERANCE: f64 = 0.1;
+
+/// Points closer than this (pixels) are merged before stroking.
+const MIN_SEGMENT: f64 = 0.25;
+
+/// Points closer than this (pixels) to a simplified path are removed before stroking.
+const COLLINEAR: f64 = 0.05;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Shape of the ends of an open path.
+pub enum LineCap {
+    /// The stroke ends exactly at the end of the path.
+    Butt,
+
+    /// The stroke ends with a half-circle.
+    Round,
+
+    /// The stroke ends with a half-square, extending past the end of the path.
+    Square,
+}
+
+impl LineCap {
+    /// Name of this line cap.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Butt => "butt",
+            Self::Round => "round",
+            Self::Square => "square",
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for LineCap {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "butt" => Ok(Self::Butt),
+            "round" => Ok(Self::Round),
+            "square" => Ok(Self::Square),
+            name => Err(PyValueError::new_err(format!("unknown line cap '{}'", name))),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Shape of the corners of a path.
+pub enum LineJoin {
+    /// Corners are sharp, unless they are too sharp (see `MITER_LIMIT`).
+    Miter,
+
+    /// Corners are rounded.
+    Round,
+
+    /// Corners are cut off.
+    Bevel,
+}
+
+impl LineJoin {
+    /// Name of this line join.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Miter => "miter",
+            Self::Round => "round",
+            Self::Bevel => "bevel",
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for LineJoin {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "miter" => Ok(Self::Miter),
+            "round" => Ok(Self::Round),
+            "bevel" => Ok(Self::Bevel),
+            name => Err(PyValueError::new_err(format!("unknown line join '{}'", name))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// A stroke style, describing how a path is outlined.
+pub struct Stroke {
+    /// Thickness of the stroke (world units), or `None` for the theme's thickness.
+    pub thickness: Option<f64>,
+
+    /// Shape of the ends of open paths (and of each dash).
+    pub cap: LineCap,
+
+    /// Shape of the corners of paths.
+    pub join: LineJoin,
+
+    /// Lengths of alternating dashes and gaps (world units), or empty for a solid stroke.
+    pub dash: Vec<f64>,
+
+    /// Distance into the dash pattern at which the path begins (world units).
+    pub dash_offset: f64,
+
+    /// Paint of the stroke, or `None` to use the color of the path.
+    pub paint: Option<Paint>,
+}
+
+impl Stroke {
+    /// Construct a new solid stroke style with round caps and joins.
+    pub fn new(thickness: Option<f64>) -> Self {
+        Self {
+            thickness,
+            cap: LineCap::Round,
+            join: LineJoin::Round,
+            dash: Vec::new(),
+            dash_offset: 0.0,
+            paint: None,
+        }
+    }
+
+    /// Set the dash pattern of this stroke.
+    /// 
+    /// The pattern gives the lengths of alternating dashes and gaps, starting with a dash.
+    /// A pattern with an odd number of lengths is repeated to make it even, and an empty
+    /// pattern gives a solid stroke.
+    pub fn set_dash(&mut self, dash: Vec<f64>) -> PyResult<()> {
+        if dash.iter().any(|length| !length.is_finite() || *length < 0.0) {
+            return Err(PyValueError::new_err("dash lengths must be non-negative"));
+        }
+
+        if !dash.is_empty() && dash.iter().sum::<f64>() <= 0.0 {
+            return Err(PyValueError::new_err("dash pattern must have a positive total length"));
+        }
+
+        self.dash = if dash.len() % 2 == 1 {
+            dash.repeat(2)
+        } else {
+            dash
+        };
+
+        Ok(())
+    }
+
+    /// Split a path (pixel coordinates) into dashes, given the number of pixels per world unit.
+    /// 
+    /// Each dash is an open path.  A solid stroke gives no dashes.
+    pub fn dashes(&self, path: &[Vector], closed: bool, scale: f64) -> Vec<Vec<Vector>> {
+        let mut dashes = Vec::new();
+
+        // Length of one repetition of the pattern
+        let period: f64 = self.dash.iter().sum::<f64>() * scale;
+        if self.dash.is_empty() || period <= 0.0 || path.is_empty() {
+            return dashes;
+        }
+
+        // Find where the path begins in the pattern
+        let mut index = 0;
+        let mut remaining = self.dash[0] * scale;
+        let mut phase = (self.dash_offset * scale).rem_euclid(period);
+        while phase > remaining {
+            phase -= remaining;
+            index = (index + 1) % self.dash.len();
+            remaining = self.dash[index] * scale;
+        }
+        remaining -= phase;
+
+        // Points along the path, returning to the start if closed
+        let mut points = path.to_vec();
+        if closed {
+            points.push(path[0]);
+        }
+
+        // Current dash (even indices are dashes, odd indices are gaps)
+        let mut current = if index % 2 == 0 { vec![points[0]] } else { Vec::new() };
+
+        for pair in points.windows(2) {
+            let (mut a, b) = (pair[0], pair[1]);
+            let mut length = (b - a).norm();
+
+            // Switch between dashes and gaps within this segment
+            while length > remaining {
+                let point = a + (b - a) * (remaining / length);
+                length -= remaining;
+                a = point;
+
+                // Finish the current dash, or begin a new one
+                current.push(point);
+                if index % 2 == 0 {
+                    dashes.push(std::mem::take(&mut current));
+                }
+
+                index = (index + 1) % self.dash.len();
+                remaining = self.dash[index] * scale;
+            }
+
+            remaining -= length;
+
+            if index % 2 == 0 {
+                current.push(b);
+            }
+        }
+
+        if !current.is_empty() {
+            dashes.push(current);
+        }
+
+        dashes
+    }
+
+    /// Construct the outline of a path (pixel coordinates) stroked with this style.
+    /// 
+    /// The outline is a set of polygons, all with the same orientation, whose union
+    /// (under the nonzero winding rule) covers the stroke exactly once.
+    pub fn outline(&self, path: &[Vector], closed: bool, width: f64) -> Vec<Vec<Vector>> {
+        let mut polygons = Vec::new();
+
+        // Half of the stroke width
+        let half = 0.5 * width;
+
+        // Merge points that are too close together
+        let mut points: Vec<Vector> = Vec::with_capacity(path.len());
+        for (i, point) in path.iter().enumerate() {
+            match points.last().copied() {
+                Some (last) if (*point - last).norm() < MIN_SEGMENT => {
+                    // Keep the end of the path exact
+                    if i + 1 == path.len() && points.len() > 1 {
+                        let n = points.len();
+                        points[n - 1] = *point;
+                    }
+                },
+                _ => points.push(*point),
+            }
+        }
+
+        // Remove points that lie on the line between their neighbors
+        let mut points = simplify(&points, COLLINEAR);
+
+        // A closed path does not need to repeat its first point
+        if closed && points.len() > 2 && (points[0] - points[points.len() - 1]).norm() < MIN_SEGMENT {
+            points.pop();
+        }
+
+        // A single point is drawn as a dot, according to its cap
+        if points.len() == 1 {
+            match self.cap {
+                LineCap::Butt => {},
+                LineCap::Round => polygons.push(disk(points[0], half)),
+                LineCap::Square => polygons.push(vec![
+                    points[0] + Vector::new(-half, -half),
+                    points[0] + Vector::new(half, -half),
+                    points[0] + Vector::new(half, half),
+                    points[0] + Vector::new(-half, half),
+                ]),
+            }
+
+            return orient(polygons);
+        }
+
+        if points.len() < 2 {
+            return polygons;
+        }
+
+        // Only close paths with at least three points
+        let closed = closed && points.len() > 2;
+
+        // Segments along the path
+        let count = if closed { points.len() } else { points.len() - 1 };
+        let segment = |i: usize| (points[i % points.len()], points[(i + 1) % points.len()]);
+
+        // Unit normal of each segment
+        let normals: Vec<Vector> = (0..count).map(|i| {
+            let (a, b) = segment(i);
+            let direction = (b - a) * (1.0 / (b - a).norm());
+            Vector::new(-direction.y, direction.x)
+        }).collect();
+
+        // Body of each segment
+        for (i, normal) in normals.iter().enumerate() {
+            let (a, b) = segment(i);
+            let offset = *normal * half;
+
+            polygons.push(vec![a + offset, b + offset, b - offset, a - offset]);
+        }
+
+        // Joins between segments
+        let joins = if closed { 0..count } else { 1..count };
+        for i in joins {
+            let before = normals[(i + count - 1) % count];
+            let after = normals[i];
+            let (vertex, _) = segment(i);
+
+            if let Some (join) = self.join_polygon(vertex, before, after, half) {
+                polygons.push(join);
+            }
+        }
+
+        // Caps at the ends of open paths
+        if !closed {
+            let (start, _) = segment(0);
+            let (_, end) = segment(count - 1);
+
+            // Directions pointing away from the path at each end
+            let first = normals[0];
+            let last = normals[count - 1];
+            let outward_start = Vector::new(-first.y, first.x);
+            let outward_end = Vector::new(last.y, -last.x);
+
+            for (point, outward) in [(start, outward_start), (end, outward_end)] {
+                match self.cap {
+                    LineCap::Butt => {},
+                    LineCap::Round => polygons.push(disk(point, half)),
+                    LineCap::Square => {
+                        let side = Vector::new(-outward.y, outward.x) * half;
+                        let ahead = outward * half;
+
+                        polygons.push(vec![point + side, point + side + ahead, point - side + ahead, point - side]);
+                    },
+                }
+            }
+        }
+
+        orient(polygons)
+    }
+
+    /// Construct the polygon joining two segments at a vertex, given their unit normals.
+    fn join_polygon(&self, vertex: Vector, before: Vector, after: Vector, half: f64) -> Option<Vec<Vector>> {
+        // Turning direction; the join is drawn on the outside of the turn
+        let turn = before.cross(after);
+        if turn.abs() < 1E-12 && before.dot(after) > 0.0 {
+            return None;
+        }
+        let side = if turn > 0.0 { -1.0 } else { 1.0 };
+
+        // Outer corners of the two segments
+        let a = vertex + before * (half * side);
+        let b = vertex + after * (half * side);
+
+        // Length of the miter, relative to half the stroke width
+        let bisector = before + after;
+        let ratio = if bisector.norm() > 1E-12 { 2.0 / bisector.norm() } else { f64::INFINITY };
+        let miter = vertex + bisector * (half * side * ratio / bisector.norm().max(1E-12));
+
+        // Gentle corners are indistinguishable from miter joins, which are cheapest
+        let gentle = half * (ratio - 1.0) < ROUND_TOLERANCE;
+
+        match self.join {
+            _ if gentle => Some (vec![vertex, a, miter, b]),
+            LineJoin::Miter if ratio <= MITER_LIMIT => Some (vec![vertex, a, miter, b]),
+            LineJoin::Miter | LineJoin::Bevel => Some (vec![vertex, a, b]),
+            LineJoin::Round => Some (disk(vertex, half)),
+        }
+    }
+}
+
+/// Simplify a path using the Douglas-Peucker algorithm, so that no removed point is
+/// farther than `tolerance` from the simplified path.
+fn simplify(points: &[Vector], tolerance: f64) -> Vec<Vector> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    // Which points are kept?
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    // Ranges of points left to simplify
+    let mut ranges = vec![(0, points.len() - 1)];
+
+    while let Some ((first, last)) = ranges.pop() {
+        let chord = points[last] - points[first];
+        let length = chord.norm();
+
+        // Find the point farthest from the chord
+        let mut farthest = (0.0, first);
+        for (i, point) in points.iter().enumerate().take(last).skip(first + 1) {
+            let offset = *point - points[first];
+            let distance = if length > 0.0 {
+                // Distance from the chord, including past its ends
+                let along = (chord.dot(offset) / (length * length)).clamp(0.0, 1.0);
+                (offset - chord * along).norm()
+            } else {
+                offset.norm()
+            };
+
+            if distance > farthest.0 {
+                farthest = (distance, i);
+            }
+        }
+
+        // Keep the farthest point if it is too far, and simplify on either side of it
+        if farthest.0 > tolerance {
+            keep[farthest.1] = true;
+            ranges.push((first, farthest.1));
+            ranges.push((farthest.1, last));
+        }
+    }
+
+    points.iter().zip(keep).filter(|(_, keep)| *keep).map(|(point, _)| *point).collect()
+}
+
+/// Construct a polygon approximating a disk.
+fn disk(center: Vector, radius: f64) -> Vec<Vector> {
+    // Number of sides, so that the polygon is within tolerance of the disk
+    let sides = if radius > ROUND_TOLERANCE {
+        (PI / (1.0 - ROUND_TOLERANCE / radius).acos()).ceil().clamp(8.0, 256.0) as usize
+    } else {
+        8
+    };
+
+    (0..sides).map(|k| {
+        let angle = 2.0 * PI * (k as f64) / (sides as f64);
+        center + Vector::new(angle.cos(), angle.sin()) * radius
+    }).collect()
+}
+
+/// Compute the signed area of a polygon.
+pub fn signed_area(polygon: &[Vector]) -> f64 {
+    let mut area = 0.0;
+
+    for i in 0..polygon.len() {
+        area += polygon[i].cross(polygon[(i + 1) % polygon.len()]);
+    }
+
+    0.5 * area
+}
+
+/// Give every polygon the same (positive) orientation.
+fn orient(mut polygons: Vec<Vec<Vector>>) -> Vec<Vec<Vector>> {
+    for polygon in polygons.iter_mut() {
+        if signed_area(polygon) < 0.0 {
+            polygon.reverse();
+        }
+    }
+
+    polygons
+}
\ No newline at end of file
@@ -1,15 +1,10 @@
 //! Trace a shape.
 
-use image::RgbImage;
-
 use crate::{
-    add_pixel,
     Artist,
     Animate,
-    Bresenham,
-    Brush,
+    Frame,
     Shape,
-    STEP,
     Vector,
 };
 
@@ -67,60 +62,32 @@ impl TracedShape {
 }
 
 impl Artist for TracedShape {
-    fn draw(&self, location: Vector, image: &mut RgbImage) {
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        let shape = self.shape.sized(frame);
+
         // Amount of progress per curve
-        let progress_per_curve = 1.0 / (self.shape.curves.len() as f64);
+        let progress_per_curve = 1.0 / (shape.curves.len() as f64);
 
-        for (i, curve) in self.shape.curves.iter().enumerate() {
+        // Range of each curve that has been traced
+        let range = |i: usize| {
             // How much progress along this curve?
             let progress = ((self.progress - i as f64 * progress_per_curve) / progress_per_curve).clamp(0.0, 1.0);
 
-            // Build collection of points to interpolate between
-            let mut t = if self.untrace { progress } else { 0.0 };
-            let mut points = Vec::new();
-
-            // Condition to finish tracing on this frame
-            let condition = |t: f64| if self.untrace { t < 1.0 } else { t <= progress };
-
-            while condition(t) {
-                // Step along the curve
-                t += STEP;
-                
-                // Fix floating-point errors
-                let t_fixed = t.clamp(0.0, 1.0 - STEP);
-
-                // Save this point
-                points.push(location + self.shape.origin + curve.trace(t_fixed));
+            if self.untrace {
+                (progress < 1.0).then_some((progress, 1.0))
+            } else {
+                (progress > 0.0).then_some((0.0, progress))
             }
+        };
 
-            // Brush to draw with
-            let brush = Brush::new(self.shape.thickness);
-
-            // If there are no points, don't draw anything
-            if points.len() == 0 {
-                continue;
-            }
+        // Fill fades in while tracing, and out while untracing
+        if let Some (fill) = &shape.fill {
+            let opacity = if self.untrace { 1.0 - self.progress } else { self.progress };
+            frame.fill(&shape.contours(location), fill, opacity);
+        }
 
-            // Interpolation (Bresenham's line algorithm)
-            for i in 0..(points.len() - 1) {
-                // Two points to draw between
-                let this_point = points[i];
-                let next_point = points[i + 1];
-
-                // Convert points to integers
-                let (x0, y0) = this_point.to_pixels(image.width(), image.height());
-                let (x1, y1) = next_point.to_pixels(image.width(), image.height());
-
-                // Construct Bresenham line
-                let line = Bresenham::new(x0, y0, x1, y1).points;
-
-                // Draw first point
-                for (x, y) in line {
-                    for (i, j, strength) in &brush.points {
-                        add_pixel(image, (x as i32 + i) as u32, (y as i32 + j) as u32, self.shape.color, *strength);
-                    }
-                }
-            }
+        for path in shape.paths(location, range) {
+            path.draw(frame);
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,35 @@
+//! An annulus.
+
+use std::f64::consts::PI;
+
+use pyo3::prelude::*;
+
+use crate::{
+    arc_curves,
+    Color,
+    Shape,
+    Vector,
+};
+
+#[pyclass(extends=Shape)]
+#[derive(Clone)]
+/// An annulus (a ring), bounded by two concentric circles.
+pub struct Annulus;
+
+#[pymethods]
+impl Annulus {
+    #[new]
+    #[pyo3(signature=(center, inner, outer, color=None, thickness=None))]
+    /// Construct a new annulus, given its inner and outer radii.
+    pub fn pynew(center: Vector, inner: f64, outer: f64, color: Option<Color>, thickness: Option<f64>) -> (Self, Shape) {
+        let color = color.map(|color| color.rgb());
+
+        // The inner circle runs the opposite way, so that it is a hole when filled
+        let mut curves = arc_curves(Vector::zero(), (outer, outer), 0.0, 2.0 * PI, color, thickness);
+        curves.extend(arc_curves(Vector::zero(), (inner, inner), 2.0 * PI, 0.0, color, thickness));
+
+        let shape = Shape::new(curves, center);
+
+        (Self {}, shape)
+    }
+}
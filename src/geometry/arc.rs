@@ -0,0 +1,74 @@
+//! A circular arc.
+
+use std::f64::consts::PI;
+
+use image::Rgb;
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    Bezier,
+    Color,
+    Shape,
+    Vector,
+};
+
+/// Approximate part of an ellipse with cubic Bezier curves.
+/// 
+/// Angles are in radians, counterclockwise from the X axis, and the arc runs from `start`
+/// to `end` (clockwise if `end < start`).  Each curve spans at most a quarter turn.
+pub fn arc_curves(center: Vector, radii: (f64, f64), start: f64, end: f64, color: Option<Rgb<u8>>, thickness: Option<f64>) -> Vec<Bezier> {
+    let (rx, ry) = radii;
+
+    // Number of curves, and the angle spanned by each
+    let count = ((end - start).abs() / (0.5 * PI)).ceil().max(1.0) as usize;
+    let step = (end - start) / count as f64;
+
+    // Length of the control arms, relative to the radius
+    let arm = 4.0 / 3.0 * (0.25 * step).tan();
+
+    // Point and tangent at an angle
+    let point = |angle: f64| center + Vector::new(rx * angle.cos(), ry * angle.sin());
+    let tangent = |angle: f64| Vector::new(-rx * angle.sin(), ry * angle.cos());
+
+    (0..count).map(|i| {
+        let a = start + step * i as f64;
+        let b = a + step;
+
+        Bezier::new(
+            vec![point(a), point(a) + tangent(a) * arm, point(b) - tangent(b) * arm, point(b)],
+            Vector::zero(),
+            color,
+            thickness,
+        )
+    }).collect()
+}
+
+#[pyclass(extends=Shape)]
+#[pyo3(name = "Arc")]
+#[derive(Clone)]
+/// An arc of a circle.
+pub struct CircularArc;
+
+#[pymethods]
+impl CircularArc {
+    #[new]
+    #[pyo3(signature=(center, radius, start, end, color=None, thickness=None))]
+    /// Construct a new arc from angle `start` to angle `end`.
+    /// 
+    /// Angles are in radians, counterclockwise from the X axis.
+    pub fn pynew(center: Vector, radius: f64, start: f64, end: f64, color: Option<Color>, thickness: Option<f64>) -> PyResult<(Self, Shape)> {
+        if !start.is_finite() || !end.is_finite() {
+            return Err(PyValueError::new_err("arc angles must be finite"));
+        }
+
+        let color = color.map(|color| color.rgb());
+
+        let shape = Shape::new(arc_curves(Vector::zero(), (radius, radius), start, end, color, thickness), center);
+
+        Ok((Self {}, shape))
+    }
+}
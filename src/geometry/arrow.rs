@@ -0,0 +1,215 @@
+//! An arrow.
+
+use image::Rgb;
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    Bezier,
+    Color,
+    Frame,
+    Shape,
+    Theme,
+    Vector,
+};
+
+/// Default length of an arrowhead, as a multiple of the arrow's thickness.
+const HEAD_LENGTH: f64 = 5.0;
+
+/// Half-width of an arrowhead, as a fraction of its length.
+const HEAD_SPREAD: f64 = 0.4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Style of an arrowhead.
+/// 
+/// From Python, this is given as `"triangle"`, `"open"`, `"bar"`, or `"none"`.
+pub enum ArrowHead {
+    /// A closed triangle, whose tip is the end of the arrow.
+    Triangle,
+
+    /// Two barbs meeting at the end of the arrow.
+    Open,
+
+    /// A bar across the end of the arrow.
+    Bar,
+
+    /// No arrowhead.
+    None,
+}
+
+impl<'py> FromPyObject<'py> for ArrowHead {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "triangle" => Ok(Self::Triangle),
+            "open" => Ok(Self::Open),
+            "bar" => Ok(Self::Bar),
+            "none" => Ok(Self::None),
+            name => Err(PyValueError::new_err(format!("unknown arrowhead '{}'", name))),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Arrowheads of an arrow without a head size, which are sized to the arrow's thickness
+/// whenever it is drawn.
+pub struct Arrowheads {
+    /// Control points of the arrow, from its start to its end.
+    pub points: Vec<Vector>,
+
+    /// Style of the arrowheads.
+    pub head: ArrowHead,
+
+    /// Is there a head at the start of the arrow as well?
+    pub double: bool,
+}
+
+impl Arrowheads {
+    /// Construct the shape of an arrow as it is drawn on a frame, with these arrowheads
+    /// sized to its thickness, in the style of the shape it was built as.
+    pub fn shape(&self, arrow: &Shape, frame: &Frame) -> Shape {
+        let length = HEAD_LENGTH * frame.thickness(arrow.stroke.thickness);
+
+        let mut shape = Arrow::shape(self.points.clone(), arrow.color, arrow.stroke.thickness, self.head, length, self.double);
+        for curve in shape.curves.iter_mut() {
+            curve.stroke = arrow.stroke.clone();
+        }
+
+        Shape {
+            stroke: arrow.stroke.clone(),
+            origin: arrow.origin,
+            fill: arrow.fill.clone(),
+            ..shape
+        }
+    }
+}
+
+#[pyclass(extends=Shape)]
+#[derive(Clone)]
+/// An arrow, which is straight, or curved along a Bezier curve.
+pub struct Arrow;
+
+#[pymethods]
+impl Arrow {
+    #[new]
+    #[pyo3(signature=(start, end, color=None, thickness=None, head=ArrowHead::Triangle, head_size=None, double=false, via=Vec::new()))]
+    /// Construct a new arrow from `start` to `end`.
+    /// 
+    /// The arrow is curved along a Bezier curve if control points are given `via`.  The
+    /// arrowhead is `head_size` long (world units), or five times the thickness it is
+    /// drawn with by default, and a `double` arrow has a head at its start as well.
+    /// 
+    /// Without a `head_size`, the arrowheads are sized when the arrow is drawn, so that
+    /// they suit the video's theme and units.  Until then (for measuring the arrow), they
+    /// are sized as if the video had no world units.
+    #[allow(clippy::too_many_arguments, reason = "style options are Python keyword arguments")]
+    pub fn pynew(
+        start: Vector,
+        end: Vector,
+        color: Option<Color>,
+        thickness: Option<f64>,
+        head: ArrowHead,
+        head_size: Option<f64>,
+        double: bool,
+        via: Vec<Vector>,
+    ) -> (Self, Shape) {
+        let color = color.map(|color| color.rgb());
+        let length = head_size.unwrap_or(HEAD_LENGTH * thickness.unwrap_or(Theme::default().thickness));
+
+        let mut points = vec![start];
+        points.extend(via);
+        points.push(end);
+
+        let mut shape = Self::shape(points.clone(), color, thickness, head, length, double);
+        if head_size.is_none() {
+            shape.arrowheads = Some (Arrowheads {
+                points,
+                head,
+                double,
+            });
+        }
+
+        (Self {}, shape)
+    }
+}
+
+impl Arrow {
+    /// Construct the shape of an arrow along a Bezier curve with the given control points.
+    pub fn shape(points: Vec<Vector>, color: Option<Rgb<u8>>, thickness: Option<f64>, head: ArrowHead, length: f64, double: bool) -> Shape {
+        let n = points.len() - 1;
+        let (start, end) = (points[0], points[n]);
+
+        // Directions the arrow points at each end
+        let direction = |from: Vector, to: Vector| {
+            let d = to - from;
+            if d.norm() > 0.0 { d * (1.0 / d.norm()) } else { Vector::new(1.0, 0.0) }
+        };
+        let forward = direction(points[n - 1], end);
+        let backward = direction(points[1], start);
+
+        let mut body = Bezier::new(points, Vector::zero(), color, thickness);
+
+        // Stop the body at the base of triangular heads, so it doesn't show through them
+        if head == ArrowHead::Triangle {
+            body = body.split(Self::reach(&body, end, length, false)).0;
+
+            if double {
+                body = body.split(Self::reach(&body, start, length, true)).1;
+            }
+        }
+
+        let mut curves = Vec::new();
+        if double {
+            curves.extend(Self::head(start, backward, head, length, color, thickness));
+        }
+        curves.push(body);
+        curves.extend(Self::head(end, forward, head, length, color, thickness));
+
+        Shape::new(curves, Vector::zero())
+    }
+
+    /// Find the parameter at which a Bezier curve comes within `length` of one of its ends
+    /// (searching from the start if `from_start`, and otherwise from the end).
+    fn reach(curve: &Bezier, tip: Vector, length: f64, from_start: bool) -> f64 {
+        let (mut near, mut far) = if from_start { (0.0, 1.0) } else { (1.0, 0.0) };
+
+        if (curve.trace(far) - tip).norm() < length {
+            return far;
+        }
+
+        for _ in 0..40 {
+            let middle = 0.5 * (near + far);
+
+            if (curve.trace(middle) - tip).norm() < length {
+                near = middle;
+            } else {
+                far = middle;
+            }
+        }
+
+        0.5 * (near + far)
+    }
+
+    /// Construct the curves of an arrowhead whose tip is at `tip`, pointing in `direction`.
+    fn head(tip: Vector, direction: Vector, head: ArrowHead, length: f64, color: Option<Rgb<u8>>, thickness: Option<f64>) -> Vec<Bezier> {
+        let normal = Vector::new(-direction.y, direction.x);
+
+        let base = tip - direction * length;
+        let left = base + normal * (HEAD_SPREAD * length);
+        let right = base - normal * (HEAD_SPREAD * length);
+
+        let line = |from: Vector, to: Vector| Bezier::new(vec![from, to], Vector::zero(), color, thickness);
+
+        match head {
+            ArrowHead::Triangle => vec![line(tip, left), line(left, right), line(right, tip)],
+            ArrowHead::Open => vec![line(left, tip), line(tip, right)],
+            ArrowHead::Bar => {
+                let spread = normal * (HEAD_SPREAD * length);
+                vec![line(tip + spread, tip - spread)]
+            },
+            ArrowHead::None => Vec::new(),
+        }
+    }
+}
@@ -1,23 +1,151 @@
 //! Bezier curve implementation.
 
-use image::{
-    Rgb,
-    RgbImage,
-};
+use image::Rgb;
 
-use pyo3::prelude::*;
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
 
 use crate::{
-    add_pixel,
     Animate,
     Artist,
-    Bresenham,
-    Brush,
+    Color,
+    Frame,
+    LineCap,
+    LineJoin,
+    Paint,
     Shape,
     STEP,
+    Stroke,
     Vector,
 };
 
+/// Number of intervals sampled when searching along a curve.
+const SAMPLES: usize = 128;
+
+/// Curves smaller than this (world units) are treated as points when intersecting.
+const TOLERANCE: f64 = 1E-6;
+
+/// Intersections closer than this (world units) are treated as the same point.
+const DISTINCT: f64 = 1E-4;
+
+/// Maximum number of subdivisions when intersecting two curves (so that curves that nearly
+/// overlap still finish).
+const MAX_SUBDIVISIONS: usize = 100_000;
+
+/// Points closer than this (world units) to a curve are treated as lying on it, when finding
+/// where curves overlap.
+const ON_CURVE: f64 = 1E-9;
+
+/// Number of intervals at which a curve is checked to lie on another.
+const OVERLAP_CHECKS: usize = 8;
+
+/// Pairs of values of `t` on two curves, at the start and end of a part where they overlap.
+type Overlap = ((f64, f64), (f64, f64));
+
+/// Find the values of `t` between 0 and 1 where a function changes sign, by bisection.
+fn roots(f: impl Fn(f64) -> f64) -> Vec<f64> {
+    let mut roots = Vec::new();
+    let at = |k: usize| k as f64 / SAMPLES as f64;
+
+    for k in 0..SAMPLES {
+        let (mut a, mut b) = (at(k), at(k + 1));
+        let (fa, fb) = (f(a), f(b));
+
+        if fa == 0.0 {
+            roots.push(a);
+            continue;
+        }
+
+        if k + 1 == SAMPLES && fb == 0.0 {
+            roots.push(b);
+        }
+
+        if fa * fb >= 0.0 {
+            continue;
+        }
+
+        for _ in 0..60 {
+            let middle = 0.5 * (a + b);
+            if (f(middle) < 0.0) == (fa < 0.0) { a = middle } else { b = middle }
+        }
+
+        roots.push(0.5 * (a + b));
+    }
+
+    roots
+}
+
+/// Remove points that are (nearly) repeated.
+pub fn distinct(points: Vec<Vector>) -> Vec<Vector> {
+    let mut output: Vec<Vector> = Vec::new();
+
+    for point in points {
+        if output.iter().all(|other| (*other - point).norm() > DISTINCT) {
+            output.push(point);
+        }
+    }
+
+    output
+}
+
+/// Find the smallest box `(min, max)` containing a series of points.
+pub fn bounds(points: impl IntoIterator<Item = Vector>) -> (Vector, Vector) {
+    let mut min = Vector::new(f64::INFINITY, f64::INFINITY);
+    let mut max = Vector::new(f64::NEG_INFINITY, f64::NEG_INFINITY);
+
+    for point in points {
+        min = Vector::new(min.x.min(point.x), min.y.min(point.y));
+        max = Vector::new(max.x.max(point.x), max.y.max(point.y));
+    }
+
+    (min, max)
+}
+
+/// Solve a small linear system with Gaussian elimination (with partial pivoting), where
+/// each right-hand side is a vector.
+fn solve(mut matrix: Vec<Vec<f64>>, mut rhs: Vec<Vector>) -> Vec<Vector> {
+    let n = rhs.len();
+
+    for col in 0..n {
+        // Pivot on the largest remaining entry in this column
+        let pivot = (col..n).max_by(|a, b| matrix[*a][col].abs().total_cmp(&matrix[*b][col].abs())).unwrap();
+        matrix.swap(col, pivot);
+        rhs.swap(col, pivot);
+
+        let pivot_row = matrix[col].clone();
+        for row in (col + 1)..n {
+            let factor = matrix[row][col] / pivot_row[col];
+            for (entry, value) in matrix[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *entry -= factor * value;
+            }
+            rhs[row] = rhs[row] - rhs[col] * factor;
+        }
+    }
+
+    // Back substitution
+    let mut solution = vec![Vector::zero(); n];
+    for row in (0..n).rev() {
+        let mut value = rhs[row];
+        for k in (row + 1)..n {
+            value = value - solution[k] * matrix[row][k];
+        }
+        solution[row] = value * (1.0 / matrix[row][row]);
+    }
+
+    solution
+}
+
+/// Check that a range of `t` values lies within a curve and runs forwards.
+fn check_range(t0: f64, t1: f64) -> PyResult<()> {
+    if !(0.0 <= t0 && t0 <= t1 && t1 <= 1.0) {
+        return Err(PyValueError::new_err("subcurve needs 0 <= t0 <= t1 <= 1"));
+    }
+
+    Ok(())
+}
+
 /// Compute the binomial coefficient C(n,k).
 fn binom(mut n: u32, k: u32) -> u32 {
     let mut output = 1;
@@ -41,50 +169,105 @@ pub struct Bezier {
     /// Origin.
     origin: Vector,
 
-    /// Color (RGB).
-    pub color: Rgb<u8>,
+    /// Color (RGB), or `None` for the theme's foreground color.
+    pub color: Option<Rgb<u8>>,
 
     /// Control points, relative to the origin.
     points: Vec<Vector>,
 
-    /// Curve thickness.
-    pub thickness: i32,
-
-    /// Curve brush.
-    brush: Brush,
+    /// Stroke style.
+    pub stroke: Stroke,
 }
 
 #[pymethods]
 impl Bezier {
     #[new]
+    #[pyo3(signature=(points, origin, color=None, thickness=None))]
     /// Construct a new Bezier curve, given control points and an origin.
     /// 
     /// Note that the control points are *relative* to the given origin.
-    pub fn pynew(points: Vec<Vector>, origin: Vector, color: [u8; 3], thickness: i32) -> (Self, Shape) {
-        let curve = Self::new(points, origin, color, thickness);
+    pub fn pynew(points: Vec<Vector>, origin: Vector, color: Option<Color>, thickness: Option<f64>) -> (Self, Shape) {
+        let curve = Self::new(points, origin, color.map(|color| color.rgb()), thickness);
         let shape = curve.get_shape();
 
         (curve, shape)
     }
-}
 
-impl Bezier {
-    /// Construct a new Bezier curve, given control points and an origin.
-    /// 
-    /// Note that the control points are *relative* to the given origin.
-    pub fn new(points: Vec<Vector>, origin: Vector, color: [u8; 3], thickness: i32) -> Self {
-        Self {
-            points,
-            origin,
-            color: Rgb (color),
-            thickness,
-            brush: Brush::new(thickness),
-        }
+    #[getter]
+    /// Shape of the ends of this curve.
+    pub fn get_cap(&self) -> &'static str {
+        self.stroke.cap.name()
     }
 
-    /// Convert this Bezier curve into a shape.
-    pub fn get_shape(&self) -> Shape {
-        Shape::new(vec![self.clone()], Vector::zero())
+    #[setter]
+    /// Set the shape of the ends of this curve (`"butt"`, `"round"`, or `"square"`).
+    pub fn set_cap(mut slf: PyRefMut<'_, Self>, cap: LineCap) {
+        slf.stroke.cap = cap;
+        slf.as_super().set_cap(cap);
+    }
+
+    #[getter]
+    /// Shape of the corners of this curve.
+    pub fn get_join(&self) -> &'static str {
+        self.stroke.join.name()
+    }
+
+    #[setter]
+    /// Set the shape of the corners of this curve (`"miter"`, `"round"`, or `"bevel"`).
+    pub fn set_join(mut slf: PyRefMut<'_, Self>, join: LineJoin) {
+        slf.stroke.join = join;
+        slf.as_super().set_join(join);
+    }
+
+    #[getter]
+    /// Paint of this curve, if it is not a solid color.
+    pub fn get_paint(&self) -> Option<Paint> {
+        self.stroke.paint.clone()
+    }
+
+    #[setter]
+    /// Set the paint of this curve (`None` for its solid color).
+    pub fn set_paint(mut slf: PyRefMut<'_, Self>, paint: Option<Paint>) {
+        slf.stroke.paint = paint.clone();
+        slf.as_super().set_paint(paint);
+    }
+
+    #[getter]
+    /// Dash pattern of this curve, as alternating dash and gap lengths (world units).
+    pub fn get_dash(&self) -> Vec<f64> {
+        self.stroke.dash.clone()
+    }
+
+    #[setter]
+    /// Set the dash pattern of this curve (an empty list gives a solid curve).
+    pub fn set_dash(mut slf: PyRefMut<'_, Self>, dash: Vec<f64>) -> PyResult<()> {
+        slf.stroke.set_dash(dash.clone())?;
+        slf.as_super().set_dash(dash)
+    }
+
+    #[getter]
+    /// Distance (world units) into the dash pattern at which this curve begins.
+    pub fn get_dash_offset(&self) -> f64 {
+        self.stroke.dash_offset
+    }
+
+    #[setter]
+    /// Set the distance (world units) into the dash pattern at which this curve begins.
+    pub fn set_dash_offset(mut slf: PyRefMut<'_, Self>, offset: f64) {
+        slf.stroke.dash_offset = offset;
+        slf.as_super().set_dash_offset(offset);
+    }
+
+    #[getter]
+    /// Control points of this curve, relative to its origin.
+    pub fn get_points(&self) -> Vec<Vector> {
+        self.points.clone()
+    }
+
+    #[getter]
+    /// Origin of this curve.
+    pub fn get_origin(&self) -> Vector {
+        self.origin
     }
 
     /// Trace this Bezier curve.
@@ -93,7 +276,7 @@ impl Bezier {
         let mut result = Vector::zero();
 
         // No curve if no points :(
-        if self.points.len() == 0 {
+        if self.points.is_empty() {
             return result;
         }
 
@@ -111,45 +294,545 @@ impl Bezier {
 
         result
     }
+
+    /// Compute the unit tangent of this curve at `t` (zero where the curve doesn't move).
+    pub fn tangent(&self, t: f64) -> Vector {
+        let velocity = self.velocity(t);
+        let speed = velocity.norm();
+
+        if speed > 0.0 { velocity * (1.0 / speed) } else { Vector::zero() }
+    }
+
+    /// Compute the unit normal of this curve at `t`, pointing to the left of its direction.
+    pub fn normal(&self, t: f64) -> Vector {
+        let tangent = self.tangent(t);
+
+        Vector::new(-tangent.y, tangent.x)
+    }
+
+    /// Compute the signed curvature of this curve at `t` (positive when turning left).
+    pub fn curvature(&self, t: f64) -> f64 {
+        let derivative = self.derivative();
+        let velocity = derivative.trace(t);
+        let acceleration = derivative.derivative().trace(t);
+        let speed = velocity.norm();
+
+        if speed > 0.0 { velocity.cross(acceleration) / speed.powi(3) } else { 0.0 }
+    }
+
+    /// Compute the length of this curve.
+    pub fn length(&self) -> f64 {
+        // Five-point Gauss-Legendre quadrature of the speed, on each of a number of intervals
+        const NODES: [(f64, f64); 5] = [
+            (0.0, 0.568_888_888_888_888_9),
+            (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+            (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+            (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+            (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+        ];
+        const INTERVALS: usize = 16;
+
+        let derivative = self.derivative();
+        let width = 1.0 / INTERVALS as f64;
+
+        (0..INTERVALS).map(|i| {
+            let middle = (i as f64 + 0.5) * width;
+            NODES.iter().map(|(x, w)| w * derivative.trace(middle + 0.5 * width * x).norm()).sum::<f64>() * 0.5 * width
+        }).sum()
+    }
+
+    /// Find the smallest box `(min, max)` containing this curve.
+    pub fn bounding_box(&self) -> (Vector, Vector) {
+        let derivative = self.derivative();
+
+        // Extremes are at the ends, or where the curve turns back horizontally or vertically
+        let mut ts = vec![0.0, 1.0];
+        ts.extend(roots(|t| derivative.trace(t).x));
+        ts.extend(roots(|t| derivative.trace(t).y));
+
+        bounds(ts.into_iter().map(|t| self.trace(t)))
+    }
+
+    /// Find the points where this curve crosses another.
+    pub fn intersections(&self, other: Bezier) -> Vec<Vector> {
+        self.crossings(&other).into_iter().map(|(t, _)| self.trace(t)).collect()
+    }
+
+    /// Find the points where this curve crosses the (infinite) line through `start` and `end`.
+    pub fn line_intersections(&self, start: Vector, end: Vector) -> Vec<Vector> {
+        let direction = end - start;
+        if direction.norm() == 0.0 {
+            return Vec::new();
+        }
+
+        let points = roots(|t| direction.cross(self.trace(t) - start)).into_iter().map(|t| self.trace(t)).collect();
+
+        distinct(points)
+    }
+
+    /// Find the point on this curve nearest to a given point.
+    pub fn nearest_point(&self, point: Vector) -> Vector {
+        self.trace(self.nearest(point))
+    }
+
+    #[pyo3(name = "split")]
+    /// Split this curve at `t` into two curves of the same degree.
+    pub fn py_split(&self, py: Python<'_>, t: f64) -> PyResult<(Py<Bezier>, Py<Bezier>)> {
+        let (first, second) = self.split(t);
+
+        Ok((first.to_python(py)?, second.to_python(py)?))
+    }
+
+    #[pyo3(name = "subcurve")]
+    /// Construct the part of this curve from `t0` to `t1`, as a curve of the same degree.
+    /// 
+    /// The range must run forwards within the curve (`0 <= t0 <= t1 <= 1`).
+    pub fn py_subcurve(&self, py: Python<'_>, t0: f64, t1: f64) -> PyResult<Py<Bezier>> {
+        check_range(t0, t1)?;
+
+        self.subcurve(t0, t1).to_python(py)
+    }
+
+    #[pyo3(name = "elevate")]
+    /// Construct the same curve with one more control point.
+    pub fn py_elevate(&self, py: Python<'_>) -> PyResult<Py<Bezier>> {
+        self.elevate().to_python(py)
+    }
+
+    #[pyo3(name = "reduce")]
+    /// Construct a curve with one fewer control point that best approximates this curve,
+    /// keeping its ends in place.
+    pub fn py_reduce(&self, py: Python<'_>) -> PyResult<Py<Bezier>> {
+        if self.points.len() < 3 {
+            return Err(PyValueError::new_err("cannot reduce the degree of a curve with fewer than 3 control points"));
+        }
+
+        self.reduce().to_python(py)
+    }
+
+    #[pyo3(name = "derivative")]
+    /// Construct the derivative (hodograph) of this curve, whose points are the velocity of this
+    /// curve with respect to `t`.
+    pub fn py_derivative(&self, py: Python<'_>) -> PyResult<Py<Bezier>> {
+        self.derivative().to_python(py)
+    }
 }
 
-impl Artist for Bezier {
-    fn draw(&self, location: Vector, image: &mut RgbImage) {
-        // Build collection of points to interpolate between
-        let mut t = 0.0;
-        let mut points = Vec::new();
+impl Bezier {
+    /// Construct a new Bezier curve, given control points and an origin.
+    /// 
+    /// Note that the control points are *relative* to the given origin.
+    pub fn new(points: Vec<Vector>, origin: Vector, color: Option<Rgb<u8>>, thickness: Option<f64>) -> Self {
+        Self {
+            points,
+            origin,
+            color,
+            stroke: Stroke::new(thickness),
+        }
+    }
 
-        while t <= 1.0 {
-            // Step along the curve
-            t += STEP;
+    /// Convert this Bezier curve into a shape.
+    pub fn get_shape(&self) -> Shape {
+        Shape::new(vec![self.clone()], Vector::zero())
+    }
 
-            // Fix floating-point errors
-            let t_fixed = t.clamp(0.0, 1.0 - STEP);
+    /// Wrap this Bezier curve into a Python object (along with its shape).
+    pub fn to_python(&self, py: Python<'_>) -> PyResult<Py<Bezier>> {
+        Py::new(py, (self.clone(), self.get_shape()))
+    }
+
+    /// Construct a copy of this Bezier curve with different control points.
+    fn with_points(&self, points: Vec<Vector>) -> Bezier {
+        let mut curve = self.clone();
+        curve.points = points;
+
+        curve
+    }
 
-            // Save this point
-            points.push(location + self.trace(t_fixed));
+    /// Split this Bezier curve at `t` into two curves of the same order, using de Casteljau's algorithm.
+    pub fn split(&self, t: f64) -> (Bezier, Bezier) {
+        let mut before = Vec::with_capacity(self.points.len());
+        let mut after = Vec::with_capacity(self.points.len());
+
+        // Repeatedly interpolate between consecutive points
+        let mut points = self.points.clone();
+        while !points.is_empty() {
+            before.push(points[0]);
+            after.push(points[points.len() - 1]);
+
+            points = points.windows(2).map(|pair| pair[0] * (1.0 - t) + pair[1] * t).collect();
+        }
+        after.reverse();
+
+        (self.with_points(before), self.with_points(after))
+    }
+
+    /// Construct the part of this Bezier curve from `t0` to `t1` (where `t0 <= t1`).
+    pub fn subcurve(&self, t0: f64, t1: f64) -> Bezier {
+        if t1 == 0.0 {
+            return self.split(0.0).0;
         }
 
-        // Interpolation (Bresenham's line algorithm)
-        for i in 0..(points.len() - 1) {
-            // Two points to draw between
-            let this_point = points[i];
-            let next_point = points[i + 1];
+        // Split off the end, then the start (rescaled to the remaining curve)
+        let (before, _) = self.split(t1);
+        before.split(t0 / t1).1
+    }
 
-            // Convert points to integers
-            let (x0, y0) = this_point.to_pixels(image.width(), image.height());
-            let (x1, y1) = next_point.to_pixels(image.width(), image.height());
+    /// Construct the same Bezier curve with one more control point.
+    pub fn elevate(&self) -> Bezier {
+        let n = self.points.len();
+        if n == 0 {
+            return self.clone();
+        }
 
-            // Construct Bresenham line
-            let line = Bresenham::new(x0, y0, x1, y1).points;
+        let points = (0..=n).map(|i| {
+            let ratio = i as f64 / n as f64;
+            let before = if i > 0 { self.points[i - 1] } else { Vector::zero() };
+            let current = if i < n { self.points[i] } else { Vector::zero() };
 
-            // Draw first point
-            for (x, y) in line {
-                for (i, j, strength) in &self.brush.points {
-                    add_pixel(image, (x as i32 + i) as u32, (y as i32 + j) as u32, self.color, *strength);
+            before * ratio + current * (1.0 - ratio)
+        }).collect();
+
+        self.with_points(points)
+    }
+
+    /// Construct a Bezier curve with one fewer control point that best approximates this one
+    /// (in the least-squares sense), keeping its ends in place.
+    /// 
+    /// Curves with fewer than three control points are returned unchanged.
+    pub fn reduce(&self) -> Bezier {
+        let n = self.points.len();
+        if n < 3 {
+            return self.clone();
+        }
+
+        // Elevating the reduced curve (with m points) should give back this curve, so
+        // point i of this curve is a blend of reduced points i - 1 and i
+        let m = n - 1;
+        let blend = |i: usize, j: usize| -> f64 {
+            let ratio = i as f64 / m as f64;
+            if j + 1 == i { ratio } else if j == i { 1.0 - ratio } else { 0.0 }
+        };
+
+        // Ends are fixed, so only the interior points are unknown
+        let (first, last) = (self.points[0], self.points[n - 1]);
+        let unknowns = m - 2;
+        let residual: Vec<Vector> = (0..n).map(|i| self.points[i] - first * blend(i, 0) - last * blend(i, m - 1)).collect();
+
+        // Normal equations of the least-squares problem
+        let mut matrix = vec![vec![0.0; unknowns]; unknowns];
+        let mut rhs = vec![Vector::zero(); unknowns];
+        for a in 0..unknowns {
+            for (i, residual) in residual.iter().enumerate() {
+                rhs[a] = rhs[a] + *residual * blend(i, a + 1);
+                for (b, entry) in matrix[a].iter_mut().enumerate() {
+                    *entry += blend(i, a + 1) * blend(i, b + 1);
                 }
             }
         }
+
+        let mut points = vec![first];
+        points.extend(solve(matrix, rhs));
+        points.push(last);
+
+        self.with_points(points)
+    }
+
+    /// Construct the derivative (hodograph) of this Bezier curve, with its origin at zero.
+    pub fn derivative(&self) -> Bezier {
+        let n = self.points.len();
+        if n < 2 {
+            return Bezier::new(vec![Vector::zero()], Vector::zero(), self.color, self.stroke.thickness);
+        }
+
+        let points = self.points.windows(2).map(|pair| (pair[1] - pair[0]) * (n - 1) as f64).collect();
+
+        let mut curve = self.with_points(points);
+        curve.origin = Vector::zero();
+
+        curve
+    }
+
+    /// Construct a copy of this Bezier curve moved by an offset.
+    pub fn moved(&self, offset: Vector) -> Bezier {
+        let mut curve = self.clone();
+        curve.origin = curve.origin + offset;
+
+        curve
+    }
+
+    /// Construct this Bezier curve traced backwards.
+    pub fn reversed(&self) -> Bezier {
+        let mut points = self.points.clone();
+        points.reverse();
+
+        self.with_points(points)
+    }
+
+    /// Construct a Bezier curve partway between this curve (at `t = 0`) and another with the
+    /// same number of control points (at `t = 1`), with its origin at zero.
+    pub fn lerp(&self, other: &Bezier, t: f64) -> Bezier {
+        let points = self.points.iter().zip(&other.points)
+            .map(|(a, b)| (*a + self.origin) * (1.0 - t) + (*b + other.origin) * t)
+            .collect();
+
+        let mut curve = self.with_points(points);
+        curve.origin = Vector::zero();
+
+        curve
+    }
+
+    /// Construct a copy of this Bezier curve with its first control point moved to a point.
+    pub fn starting_at(&self, point: Vector) -> Bezier {
+        let mut curve = self.clone();
+        if let Some (first) = curve.points.first_mut() {
+            *first = point - curve.origin;
+        }
+
+        curve
+    }
+
+    /// Construct a copy of this Bezier curve with its last control point moved to a point.
+    pub fn ending_at(&self, point: Vector) -> Bezier {
+        let mut curve = self.clone();
+        if let Some (last) = curve.points.last_mut() {
+            *last = point - curve.origin;
+        }
+
+        curve
+    }
+
+    /// Find the values of `t` on this Bezier curve and on another at which they cross, as
+    /// pairs `(t_self, t_other)`.
+    /// 
+    /// Where the curves overlap, only the ends of the overlap are given.
+    pub fn crossings(&self, other: &Bezier) -> Vec<(f64, f64)> {
+        let mut found = Vec::new();
+        let mut overlaps = Vec::new();
+        let mut budget = MAX_SUBDIVISIONS;
+        intersect((self, 0.0, 1.0), (other, 0.0, 1.0), &mut found, &mut overlaps, &mut budget);
+
+        // Join the overlapping pieces of the curves into whole overlaps, in order along this curve
+        overlaps.sort_by(|a, b| a.0.0.total_cmp(&b.0.0));
+        let mut runs: Vec<Overlap> = Vec::new();
+        for overlap in overlaps {
+            match runs.last_mut() {
+                Some (run) if overlap.0.0 <= run.1.0 || (self.trace(overlap.0.0) - self.trace(run.1.0)).norm() < DISTINCT => {
+                    if overlap.1.0 > run.1.0 {
+                        run.1 = overlap.1;
+                    }
+                },
+                _ => runs.push(overlap),
+            }
+        }
+
+        // Crossings within an overlap are where its pieces meet
+        let inside = |t: f64| runs.iter().any(|(start, end)| {
+            t > start.0 && t < end.0
+                && (self.trace(t) - self.trace(start.0)).norm() > DISTINCT
+                && (self.trace(t) - self.trace(end.0)).norm() > DISTINCT
+        });
+        let ends = runs.iter().flat_map(|(start, end)| [*start, *end]);
+        let found: Vec<(f64, f64)> = ends.chain(found.into_iter().filter(|(t, _)| !inside(*t))).collect();
+
+        // Neighboring pieces of the curves find the same crossing
+        let mut crossings: Vec<(f64, f64)> = Vec::new();
+        for (t, u) in found {
+            if crossings.iter().all(|(other, _)| (self.trace(*other) - self.trace(t)).norm() > DISTINCT) {
+                crossings.push((t, u));
+            }
+        }
+
+        crossings
+    }
+
+    /// Find the control points of this Bezier curve, moved by its origin.
+    pub fn placed_points(&self) -> Vec<Vector> {
+        self.points.iter().map(|point| *point + self.origin).collect()
+    }
+
+    /// Find the smallest box `(min, max)` containing the control points of this Bezier curve
+    /// (which contains the curve itself).
+    pub fn hull_box(&self) -> (Vector, Vector) {
+        bounds(self.placed_points())
+    }
+
+    /// Find the value of `t` at which this Bezier curve is nearest to a given point.
+    pub fn nearest(&self, point: Vector) -> f64 {
+        let distance = |t: f64| (self.trace(t) - point).norm();
+
+        // Nearest sample, then refine between its neighbors
+        let k = (0..=SAMPLES).min_by(|a, b| {
+            distance(*a as f64 / SAMPLES as f64).total_cmp(&distance(*b as f64 / SAMPLES as f64))
+        }).unwrap_or(0);
+
+        let mut a = k.saturating_sub(1) as f64 / SAMPLES as f64;
+        let mut b = (k + 1).min(SAMPLES) as f64 / SAMPLES as f64;
+
+        for _ in 0..60 {
+            let (left, right) = (a + (b - a) / 3.0, b - (b - a) / 3.0);
+            if distance(left) < distance(right) { b = right } else { a = left }
+        }
+
+        0.5 * (a + b)
+    }
+
+    /// Compute the velocity of this Bezier curve at `t`.
+    pub fn velocity(&self, t: f64) -> Vector {
+        self.derivative().trace(t)
+    }
+
+    /// Compute the arc length of this Bezier curve, from `t = start` to `t = end`.
+    pub fn arc_length(&self, start: f64, end: f64) -> f64 {
+        self.sample(start, end).windows(2).map(|pair| (pair[1] - pair[0]).norm()).sum()
+    }
+
+    /// Sample points along this Bezier curve, from `t = start` to `t = end` inclusive.
+    pub fn sample(&self, start: f64, end: f64) -> Vec<Vector> {
+        // Number of steps
+        let steps = ((end - start) / STEP).ceil().max(1.0) as usize;
+
+        (0..=steps).map(|k| self.trace(start + (end - start) * (k as f64) / (steps as f64))).collect()
+    }
+}
+
+/// Find the intersections of two Bezier curves by recursively subdividing them wherever
+/// their control points' bounding boxes overlap.
+/// 
+/// Each curve is given with the range of `t` it covers on its original curve, and each
+/// intersection is recorded as a pair of values of `t` on the original curves.  Where one
+/// piece lies on the other, the curves overlap rather than cross, and the ends of the
+/// overlapping piece are recorded as pairs of values of `t`, in order along the first curve.
+fn intersect(a: (&Bezier, f64, f64), b: (&Bezier, f64, f64), found: &mut Vec<(f64, f64)>, overlaps: &mut Vec<Overlap>, budget: &mut usize) {
+    let ((a, a0, a1), (b, b0, b1)) = (a, b);
+
+    if *budget == 0 {
+        return;
+    }
+    *budget -= 1;
+
+    let (a_min, a_max) = a.hull_box();
+    let (b_min, b_max) = b.hull_box();
+
+    if a_max.x < b_min.x || b_max.x < a_min.x || a_max.y < b_min.y || b_max.y < a_min.y {
+        return;
+    }
+
+    if separated(a, b) || separated(b, a) {
+        return;
+    }
+
+    if let Some ((u0, u1)) = lies_on(a, b) {
+        overlaps.push(((a0, b0 + u0 * (b1 - b0)), (a1, b0 + u1 * (b1 - b0))));
+        return;
+    }
+
+    if let Some ((t0, t1)) = lies_on(b, a) {
+        let (t0, t1) = (a0 + t0 * (a1 - a0), a0 + t1 * (a1 - a0));
+        overlaps.push(if t0 <= t1 { ((t0, b0), (t1, b1)) } else { ((t1, b1), (t0, b0)) });
+        return;
+    }
+
+    let a_size = (a_max.x - a_min.x).max(a_max.y - a_min.y);
+    let b_size = (b_max.x - b_min.x).max(b_max.y - b_min.y);
+
+    if a_size < TOLERANCE && b_size < TOLERANCE {
+        found.push((0.5 * (a0 + a1), 0.5 * (b0 + b1)));
+        return;
+    }
+
+    // Split the larger curve in half
+    if a_size >= b_size {
+        let (first, second) = a.split(0.5);
+        let middle = 0.5 * (a0 + a1);
+        intersect((&first, a0, middle), (b, b0, b1), found, overlaps, budget);
+        intersect((&second, middle, a1), (b, b0, b1), found, overlaps, budget);
+    } else {
+        let (first, second) = b.split(0.5);
+        let middle = 0.5 * (b0 + b1);
+        intersect((a, a0, a1), (&first, b0, middle), found, overlaps, budget);
+        intersect((a, a0, a1), (&second, middle, b1), found, overlaps, budget);
+    }
+}
+
+/// Find the band around a Bezier curve's chord (its "fat line") that contains the curve, as
+/// a point on the chord, a unit normal to it, and the least and greatest signed distances of
+/// the curve's control points from it.
+/// 
+/// Curves whose ends meet have no chord, and no band.
+fn fat_line(curve: &Bezier) -> Option<(Vector, Vector, f64, f64)> {
+    let points = curve.placed_points();
+    let (first, last) = (*points.first()?, *points.last()?);
+
+    let chord = last - first;
+    if chord.norm() < TOLERANCE {
+        return None;
+    }
+
+    let normal = Vector::new(-chord.y, chord.x) * (1.0 / chord.norm());
+    let (min, max) = points.iter()
+        .map(|point| normal.dot(*point - first))
+        .fold((0.0_f64, 0.0_f64), |(min, max), d| (min.min(d), max.max(d)));
+
+    Some ((first, normal, min, max))
+}
+
+/// Is one Bezier curve entirely to one side of the band containing another?
+fn separated(curve: &Bezier, other: &Bezier) -> bool {
+    let Some ((first, normal, min, max)) = fat_line(other) else {
+        return false;
+    };
+
+    let distances: Vec<f64> = curve.placed_points().iter().map(|point| normal.dot(*point - first)).collect();
+
+    distances.iter().all(|d| *d < min - ON_CURVE) || distances.iter().all(|d| *d > max + ON_CURVE)
+}
+
+/// Does all of one Bezier curve lie on another?  If so, find the values of `t` on the other
+/// curve at the start and end of the first.
+fn lies_on(curve: &Bezier, other: &Bezier) -> Option<(f64, f64)> {
+    let (min, max) = other.hull_box();
+    let band = fat_line(other);
+
+    // Check the ends first, since most curves that don't lie on the other fail there
+    let checks = [0, OVERLAP_CHECKS].into_iter().chain(1..OVERLAP_CHECKS);
+    let mut ends = (0.0, 0.0);
+
+    for k in checks {
+        let point = curve.trace(k as f64 / OVERLAP_CHECKS as f64);
+
+        if point.x < min.x - ON_CURVE || point.x > max.x + ON_CURVE || point.y < min.y - ON_CURVE || point.y > max.y + ON_CURVE {
+            return None;
+        }
+
+        if let Some ((first, normal, low, high)) = band {
+            let d = normal.dot(point - first);
+            if d < low - ON_CURVE || d > high + ON_CURVE {
+                return None;
+            }
+        }
+
+        let t = other.nearest(point);
+        if (other.trace(t) - point).norm() > ON_CURVE {
+            return None;
+        }
+
+        if k == 0 {
+            ends.0 = t;
+        } else if k == OVERLAP_CHECKS {
+            ends.1 = t;
+        }
+    }
+
+    Some (ends)
+}
+
+impl Artist for Bezier {
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        // Build collection of points to interpolate between
+        let points: Vec<Vector> = self.sample(0.0, 1.0).into_iter().map(|point| location + point).collect();
+
+        frame.stroke(&points, &[], false, &self.stroke, self.color);
     }
 }
 
@@ -161,4 +844,35 @@ impl Animate for Bezier {
     fn clone_box(&self) -> Box<dyn Animate> {
         Box::new(self.clone())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subcurve() {
+        let curve = Bezier::new(
+            vec![Vector::new(0.0, 0.0), Vector::new(1.0, 2.0), Vector::new(3.0, -1.0), Vector::new(4.0, 1.0)],
+            Vector::zero(),
+            None,
+            None,
+        );
+
+        // The piece follows the curve between its ends
+        let piece = curve.subcurve(0.25, 0.75);
+        for k in 0..=10 {
+            let t = k as f64 / 10.0;
+            assert!((piece.trace(t) - curve.trace(0.25 + 0.5 * t)).norm() < 1E-9);
+        }
+
+        // A range that is empty, or starts at the beginning of the curve, is still allowed
+        assert!((curve.subcurve(0.0, 0.0).trace(1.0) - curve.trace(0.0)).norm() < 1E-9);
+        assert!(check_range(0.0, 0.0).is_ok() && check_range(0.5, 0.5).is_ok() && check_range(0.0, 1.0).is_ok());
+
+        // Backwards ranges, and ranges outside the curve, are rejected
+        assert!(check_range(0.75, 0.25).is_err());
+        assert!(check_range(-0.5, 0.5).is_err());
+        assert!(check_range(0.5, 1.5).is_err());
+        assert!(check_range(f64::NAN, 0.5).is_err());
+    }
+}
@@ -0,0 +1,373 @@
+//! Boolean operations (union, intersection, difference, and exclusive or) on shapes.
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    signed_area,
+    Bezier,
+    Shape,
+    Vector,
+};
+
+/// Pieces shorter than this (world units) are dropped.
+const TINY: f64 = 1E-6;
+
+/// Pieces whose ends are closer than this (world units) are joined.
+const JOINED: f64 = 1E-3;
+
+/// Values of `t` closer than this to the ends of a curve are treated as its ends.
+const END: f64 = 1E-9;
+
+/// Pieces whose middles are closer than this (world units) to the other shape's outline
+/// lie on that outline.
+const ON_OUTLINE: f64 = 1E-6;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// A boolean operation combining the insides of two shapes.
+pub enum BooleanOp {
+    /// Inside either shape.
+    Union,
+
+    /// Inside both shapes.
+    Intersection,
+
+    /// Inside the first shape but not the second.
+    Difference,
+
+    /// Inside exactly one of the shapes.
+    Xor,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Where a piece of one shape's outline lies, compared to the other shape.
+enum Side {
+    /// Inside the other shape.
+    Inside,
+
+    /// Outside the other shape.
+    Outside,
+
+    /// On the other shape's outline, running the same way (so both insides are on the same side).
+    Same,
+
+    /// On the other shape's outline, running the opposite way (so the insides are on opposite sides).
+    Opposite,
+}
+
+/// A piece of a shape's outline, between the places it crosses (or starts or stops
+/// overlapping) the other shape's outline.
+struct Piece {
+    /// Curve of this piece (world coordinates).
+    curve: Bezier,
+
+    /// Where this piece lies, compared to the other shape.
+    side: Side,
+}
+
+/// Combine the insides of two shapes with a boolean operation, giving a new shape with the
+/// color, stroke, and fill of the first.
+///
+/// Both shapes must be made of closed outlines.  The outline of the result is made of the
+/// pieces of each outline that lie inside or outside the other shape, joined into loops.
+/// Where the outlines overlap, the shared pieces are kept (once) if they bound the result.
+pub fn combine(a: &Shape, b: &Shape, op: BooleanOp) -> PyResult<Shape> {
+    if !closed(a) || !closed(b) {
+        return Err(PyValueError::new_err("boolean operations need shapes made of closed outlines"));
+    }
+
+    // Give both shapes the same orientation, so that pieces of one join pieces of the other
+    let a_curves = a.placed();
+    let mut b_curves = b.placed();
+    if area(&a_curves) * area(&b_curves) < 0.0 {
+        b_curves = b_curves.iter().rev().map(|curve| curve.reversed()).collect();
+    }
+
+    // Values of `t` at which each curve crosses the other shape
+    let mut a_cuts = vec![Vec::new(); a_curves.len()];
+    let mut b_cuts = vec![Vec::new(); b_curves.len()];
+    for (i, curve) in a_curves.iter().enumerate() {
+        for (j, other) in b_curves.iter().enumerate() {
+            for (t, u) in curve.crossings(other) {
+                a_cuts[i].push(t);
+                b_cuts[j].push(u);
+            }
+        }
+    }
+
+    let a_pieces = pieces(&a_curves, a_cuts, b, &b_curves);
+    let b_pieces = pieces(&b_curves, b_cuts, a, &a_curves);
+
+    // Keep the pieces of each outline bounding the result (reversed if the result is on their outside)
+    let select = |pieces: &[Piece], sides: &[Side], reverse: bool| -> Vec<Bezier> {
+        pieces.iter()
+            .filter(|piece| sides.contains(&piece.side))
+            .map(|piece| if reverse { piece.curve.reversed() } else { piece.curve.clone() })
+            .collect()
+    };
+
+    // Shared pieces are taken from only one of the shapes.  Those running the same way bound
+    // the union and intersection, and those running opposite ways bound each difference.
+    let loops = match op {
+        BooleanOp::Union => join([
+            select(&a_pieces, &[Side::Outside, Side::Same], false),
+            select(&b_pieces, &[Side::Outside], false),
+        ].concat()),
+        BooleanOp::Intersection => join([
+            select(&a_pieces, &[Side::Inside, Side::Same], false),
+            select(&b_pieces, &[Side::Inside], false),
+        ].concat()),
+        BooleanOp::Difference => join([
+            select(&a_pieces, &[Side::Outside, Side::Opposite], false),
+            select(&b_pieces, &[Side::Inside], true),
+        ].concat()),
+        BooleanOp::Xor => {
+            let mut loops = join([
+                select(&a_pieces, &[Side::Outside, Side::Opposite], false),
+                select(&b_pieces, &[Side::Inside], true),
+            ].concat());
+            loops.extend(join([
+                select(&b_pieces, &[Side::Outside, Side::Opposite], false),
+                select(&a_pieces, &[Side::Inside], true),
+            ].concat()));
+            loops
+        },
+    };
+
+    // Place the result at the first shape's origin, in its style
+    let curves = loops.into_iter().flatten()
+        .map(|curve| {
+            let mut curve = curve.moved(-a.origin);
+            curve.color = a.color;
+            curve.stroke = a.stroke.clone();
+            curve
+        })
+        .collect();
+
+    Ok(Shape {
+        curves,
+        stroke: a.stroke.clone(),
+        origin: a.origin,
+        color: a.color,
+        fill: a.fill.clone(),
+        arrowheads: None,
+    })
+}
+
+/// Is every outline of a shape closed?
+fn closed(shape: &Shape) -> bool {
+    shape.contours(Vector::zero()).iter().all(|contour| (contour[0] - contour[contour.len() - 1]).norm() < JOINED)
+}
+
+/// Find the (signed) area enclosed by a series of curves, which is positive when they run counterclockwise.
+fn area(curves: &[Bezier]) -> f64 {
+    let points: Vec<Vector> = curves.iter().flat_map(|curve| curve.sample(0.0, 1.0)).collect();
+
+    signed_area(&points)
+}
+
+/// Cut each curve of an outline where it crosses the other shape, and find where each
+/// piece lies compared to the other shape (whose placed and oriented curves are given).
+fn pieces(curves: &[Bezier], cuts: Vec<Vec<f64>>, other: &Shape, other_curves: &[Bezier]) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+
+    for (curve, mut cuts) in curves.iter().zip(cuts) {
+        cuts.retain(|t| *t > END && *t < 1.0 - END);
+        cuts.sort_by(f64::total_cmp);
+
+        let mut ts = vec![0.0];
+        ts.extend(cuts);
+        ts.push(1.0);
+
+        for pair in ts.windows(2) {
+            let piece = curve.subcurve(pair[0], pair[1]);
+            if piece.length() < TINY {
+                continue;
+            }
+
+            pieces.push(Piece {
+                side: side(&piece, other, other_curves),
+                curve: piece,
+            });
+        }
+    }
+
+    pieces
+}
+
+/// Find where a piece of one shape's outline lies, compared to the other shape (whose placed
+/// and oriented curves are given).
+fn side(piece: &Bezier, other: &Shape, other_curves: &[Bezier]) -> Side {
+    let middle = piece.trace(0.5);
+
+    // Nearest point on the other outline, and the direction it runs there
+    let nearest = other_curves.iter()
+        .map(|curve| {
+            let t = curve.nearest(middle);
+            ((curve.trace(t) - middle).norm(), curve.velocity(t))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0));
+
+    match nearest {
+        Some ((distance, direction)) if distance < ON_OUTLINE => {
+            if piece.velocity(0.5).dot(direction) > 0.0 { Side::Same } else { Side::Opposite }
+        },
+        _ if other.contains(middle) => Side::Inside,
+        _ => Side::Outside,
+    }
+}
+
+/// Join pieces of outlines end to start into closed loops.
+///
+/// Pieces that cannot be closed into a loop are left out.
+fn join(mut pieces: Vec<Bezier>) -> Vec<Vec<Bezier>> {
+    let mut loops = Vec::new();
+
+    while !pieces.is_empty() {
+        let mut chain = vec![pieces.remove(0)];
+        let start = chain[0].trace(0.0);
+
+        loop {
+            let end = chain[chain.len() - 1].trace(1.0);
+            if chain.len() > 1 && (end - start).norm() < JOINED {
+                // Make the loop meet exactly where it began
+                let last = chain.len() - 1;
+                chain[last] = chain[last].ending_at(start);
+                loops.push(chain);
+                break;
+            }
+
+            // Next piece, beginning nearest where this one ends
+            let next = pieces.iter().enumerate()
+                .map(|(k, piece)| (k, (piece.trace(0.0) - end).norm()))
+                .filter(|(_, distance)| *distance < JOINED)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+
+            match next {
+                Some ((k, _)) => {
+                    let piece = pieces.remove(k);
+                    chain.push(piece.starting_at(end));
+                },
+                None => {
+                    // A whole outline that was never cut closes on itself
+                    if (end - start).norm() < JOINED {
+                        loops.push(chain);
+                    }
+                    break;
+                },
+            }
+        }
+    }
+
+    loops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rectangle;
+
+    /// Tolerance on the areas and bounds of results.
+    const CLOSE: f64 = 1E-4;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Shape {
+        Rectangle::pynew(Vector::new(x, y), width, height, None, None).1
+    }
+
+    /// Area of a result, splitting its outline into the loops it is made of.
+    fn total_area(shape: &Shape) -> f64 {
+        let curves = shape.placed();
+        let mut total = 0.0;
+        let mut start = 0;
+
+        for k in 0..curves.len() {
+            let closes = k + 1 == curves.len() || (curves[k].trace(1.0) - curves[k + 1].trace(0.0)).norm() > JOINED;
+            if closes {
+                total += area(&curves[start..=k]);
+                start = k + 1;
+            }
+        }
+
+        total.abs()
+    }
+
+    fn check(a: &Shape, b: &Shape, op: BooleanOp, expected: f64) -> Shape {
+        let result = combine(a, b, op).unwrap();
+        let found = total_area(&result);
+        assert!((found - expected).abs() < CLOSE, "{op:?}: area {found}, expected {expected}");
+
+        result
+    }
+
+    #[test]
+    fn crossing() {
+        let (a, b) = (rect(0.0, 0.0, 2.0, 2.0), rect(1.0, 1.0, 2.0, 2.0));
+
+        check(&a, &b, BooleanOp::Union, 7.0);
+        check(&a, &b, BooleanOp::Intersection, 1.0);
+        check(&a, &b, BooleanOp::Difference, 3.0);
+        check(&a, &b, BooleanOp::Xor, 6.0);
+    }
+
+    #[test]
+    fn nested() {
+        let (a, b) = (rect(0.0, 0.0, 4.0, 4.0), rect(0.0, 0.0, 2.0, 2.0));
+
+        check(&a, &b, BooleanOp::Union, 16.0);
+        check(&a, &b, BooleanOp::Intersection, 4.0);
+        check(&a, &b, BooleanOp::Difference, 12.0);
+        check(&a, &b, BooleanOp::Xor, 12.0);
+        assert!(combine(&b, &a, BooleanOp::Difference).unwrap().curves.is_empty());
+    }
+
+    #[test]
+    fn disjoint() {
+        let (a, b) = (rect(0.0, 0.0, 2.0, 2.0), rect(5.0, 0.0, 2.0, 2.0));
+
+        check(&a, &b, BooleanOp::Union, 8.0);
+        check(&a, &b, BooleanOp::Difference, 4.0);
+        check(&a, &b, BooleanOp::Xor, 8.0);
+        assert!(combine(&a, &b, BooleanOp::Intersection).unwrap().curves.is_empty());
+    }
+
+    #[test]
+    fn touching() {
+        let (a, b) = (rect(0.0, 0.0, 2.0, 2.0), rect(2.0, 0.0, 2.0, 2.0));
+
+        let union = check(&a, &b, BooleanOp::Union, 8.0);
+        let (min, max) = union.bounding_box();
+        assert!((min - Vector::new(-1.0, -1.0)).norm() < CLOSE && (max - Vector::new(3.0, 1.0)).norm() < CLOSE);
+
+        check(&a, &b, BooleanOp::Difference, 4.0);
+        check(&a, &b, BooleanOp::Xor, 8.0);
+        assert!(combine(&a, &b, BooleanOp::Intersection).unwrap().curves.is_empty());
+    }
+
+    #[test]
+    fn shared_edges() {
+        let (a, b) = (rect(0.0, 0.0, 2.0, 2.0), rect(1.0, 0.0, 2.0, 2.0));
+
+        let union = check(&a, &b, BooleanOp::Union, 6.0);
+        let (min, max) = union.bounding_box();
+        assert!((min - Vector::new(-1.0, -1.0)).norm() < CLOSE && (max - Vector::new(2.0, 1.0)).norm() < CLOSE);
+
+        let intersection = check(&a, &b, BooleanOp::Intersection, 2.0);
+        let (min, max) = intersection.bounding_box();
+        assert!((min - Vector::new(0.0, -1.0)).norm() < CLOSE && (max - Vector::new(1.0, 1.0)).norm() < CLOSE);
+
+        check(&a, &b, BooleanOp::Difference, 2.0);
+        check(&a, &b, BooleanOp::Xor, 4.0);
+    }
+
+    #[test]
+    fn identical() {
+        let a = rect(0.0, 0.0, 2.0, 2.0);
+
+        check(&a, &a, BooleanOp::Union, 4.0);
+        check(&a, &a, BooleanOp::Intersection, 4.0);
+        assert!(combine(&a, &a, BooleanOp::Difference).unwrap().curves.is_empty());
+        assert!(combine(&a, &a, BooleanOp::Xor).unwrap().curves.is_empty());
+    }
+}
@@ -4,6 +4,7 @@ use pyo3::prelude::*;
 
 use crate::{
     Bezier,
+    Color,
     Shape,
     Vector,
 };
@@ -19,8 +20,11 @@ pub struct Circle;
 #[pymethods]
 impl Circle {
     #[new]
+    #[pyo3(signature=(center, radius, color=None, thickness=None))]
     /// Construct a new circle.
-    pub fn pynew(center: Vector, radius: f64, color: [u8; 3], thickness: i32) -> (Self, Shape) {
+    pub fn pynew(center: Vector, radius: f64, color: Option<Color>, thickness: Option<f64>) -> (Self, Shape) {
+        let color = color.map(|color| color.rgb());
+
         // Magic steps
         let xstep = Vector::new(radius*MAGIC, 0.0);
         let ystep = Vector::new(0.0, radius*MAGIC);
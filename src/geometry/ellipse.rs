@@ -0,0 +1,31 @@
+//! An ellipse.
+
+use std::f64::consts::PI;
+
+use pyo3::prelude::*;
+
+use crate::{
+    arc_curves,
+    Color,
+    Shape,
+    Vector,
+};
+
+#[pyclass(extends=Shape)]
+#[derive(Clone)]
+/// An axis-aligned ellipse.
+pub struct Ellipse;
+
+#[pymethods]
+impl Ellipse {
+    #[new]
+    #[pyo3(signature=(center, x_radius, y_radius, color=None, thickness=None))]
+    /// Construct a new ellipse, given its radii along the X and Y axes.
+    pub fn pynew(center: Vector, x_radius: f64, y_radius: f64, color: Option<Color>, thickness: Option<f64>) -> (Self, Shape) {
+        let color = color.map(|color| color.rgb());
+
+        let shape = Shape::new(arc_curves(Vector::zero(), (x_radius, y_radius), 0.0, 2.0 * PI, color, thickness), center);
+
+        (Self {}, shape)
+    }
+}
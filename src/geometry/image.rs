@@ -0,0 +1,277 @@
+//! A raster image.
+
+use std::sync::Arc;
+
+use image::{
+    Rgb,
+    RgbaImage,
+};
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    Animate,
+    Animation,
+    Artist,
+    Frame,
+    Vector,
+};
+
+#[pyclass]
+#[derive(Clone)]
+/// A raster image (PNG or JPEG), placed in the world.
+pub struct Image {
+    /// Path the image was loaded from.
+    path: String,
+
+    /// Image pixels (sRGB, with straight alpha), shared between copies of this image.
+    pixels: Arc<RgbaImage>,
+
+    #[pyo3(get, set)]
+    /// Center of the image.
+    pub position: Vector,
+
+    #[pyo3(get, set)]
+    /// Width of the image (world units).
+    pub width: f64,
+
+    #[pyo3(get, set)]
+    /// Height of the image (world units).
+    pub height: f64,
+
+    #[pyo3(get, set)]
+    /// Counterclockwise rotation of the image about its center (radians).
+    pub rotation: f64,
+
+    #[pyo3(get)]
+    /// Opacity of the image (between 0 and 1).
+    pub opacity: f64,
+}
+
+#[pymethods]
+impl Image {
+    #[new]
+    #[pyo3(signature=(path, position, width=None, height=None, rotation=0.0, opacity=1.0))]
+    /// Load an image from a file and center it at a position.
+    ///
+    /// If only one of `width` and `height` is given, the other is chosen to keep the
+    /// image's aspect ratio.  If neither is given, each pixel is one world unit.
+    pub fn new(path: String, position: Vector, width: Option<f64>, height: Option<f64>, rotation: f64, opacity: f64) -> PyResult<Self> {
+        let pixels = Self::load(&path)?;
+        let aspect = pixels.width() as f64 / pixels.height() as f64;
+
+        let (width, height) = match (width, height) {
+            (Some (width), Some (height)) => (width, height),
+            (Some (width), None) => (width, width / aspect),
+            (None, Some (height)) => (height * aspect, height),
+            (None, None) => (pixels.width() as f64, pixels.height() as f64),
+        };
+
+        Ok(Self {
+            path,
+            pixels: Arc::new(pixels),
+            position,
+            width,
+            height,
+            rotation,
+            opacity: opacity.clamp(0.0, 1.0),
+        })
+    }
+
+    #[getter]
+    /// Path this image was loaded from.
+    pub fn get_path(&self) -> String {
+        self.path.clone()
+    }
+
+    #[setter]
+    /// Set the opacity of the image (clamped between 0 and 1).
+    pub fn set_opacity(&mut self, opacity: f64) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    #[getter]
+    /// Size of this image (pixels).
+    pub fn get_size(&self) -> (u32, u32) {
+        self.pixels.dimensions()
+    }
+
+    #[getter]
+    /// Construct a (static) animation from this image.
+    pub fn get_display(&self) -> Animation {
+        Animate::animate(self)
+    }
+
+    #[getter]
+    /// Construct an animation that fades this image in.
+    pub fn get_fade_in(&self) -> Animation {
+        Fade::new(self.clone(), false).animate()
+    }
+
+    #[getter]
+    /// Construct an animation that fades this image out.
+    pub fn get_fade_out(&self) -> Animation {
+        Fade::new(self.clone(), true).animate()
+    }
+}
+
+impl Image {
+    /// Load the pixels of an image file.
+    pub fn load(path: &str) -> PyResult<RgbaImage> {
+        let pixels = image::open(path)
+            .map_err(|error| PyValueError::new_err(format!("could not load image '{}': {}", path, error)))?
+            .to_rgba8();
+
+        if pixels.width() == 0 || pixels.height() == 0 {
+            return Err(PyValueError::new_err(format!("image '{}' is empty", path)));
+        }
+
+        Ok(pixels)
+    }
+
+    /// Sample this image with bilinear filtering at a point in pixel coordinates, giving
+    /// a color and its opacity (transparent outside the image).
+    pub fn sample(&self, u: f64, v: f64) -> (Rgb<u8>, f64) {
+        let (w, h) = (self.pixels.width() as i64, self.pixels.height() as i64);
+
+        // Pixel centers surrounding this point, and the weight of each
+        let (u, v) = (u - 0.5, v - 0.5);
+        let (x, y) = (u.floor() as i64, v.floor() as i64);
+        let (fx, fy) = (u - x as f64, v - y as f64);
+
+        let mut sum = [0.0; 3];
+        let mut alpha = 0.0;
+
+        for (dx, dy, weight) in [(0, 0, (1.0 - fx) * (1.0 - fy)), (1, 0, fx * (1.0 - fy)), (0, 1, (1.0 - fx) * fy), (1, 1, fx * fy)] {
+            let (px, py) = (x + dx, y + dy);
+
+            if px < 0 || py < 0 || px >= w || py >= h {
+                continue;
+            }
+
+            let pixel = self.pixels.get_pixel(px as u32, py as u32);
+            let a = weight * pixel[3] as f64 / 255.0;
+
+            for c in 0..3 {
+                sum[c] += a * pixel[c] as f64;
+            }
+            alpha += a;
+        }
+
+        if alpha <= 0.0 {
+            return (Rgb ([0, 0, 0]), 0.0);
+        }
+
+        (Rgb (sum.map(|s| (s / alpha).round().clamp(0.0, 255.0) as u8)), alpha)
+    }
+
+    /// Draw this image stretched to cover a whole frame (cropping it to keep its aspect
+    /// ratio), regardless of the camera.
+    pub fn cover(&self, frame: &mut Frame) {
+        let (width, height) = frame.image.dimensions();
+        let (w, h) = (self.pixels.width() as f64, self.pixels.height() as f64);
+
+        // Image pixels per frame pixel, and the offset of the crop
+        let ratio = (w / width as f64).min(h / height as f64);
+        let (ox, oy) = (0.5 * (w - width as f64 * ratio), 0.5 * (h - height as f64 * ratio));
+
+        for y in 0..height {
+            for x in 0..width {
+                let (color, alpha) = self.sample(ox + (x as f64 + 0.5) * ratio, oy + (y as f64 + 0.5) * ratio);
+
+                if alpha > 0.0 {
+                    frame.add_pixel(x, y, color, alpha * self.opacity);
+                }
+            }
+        }
+    }
+}
+
+impl Artist for Image {
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        if self.opacity <= 0.0 || self.width == 0.0 || self.height == 0.0 {
+            return;
+        }
+
+        let center = location + self.position;
+        let (sin, cos) = self.rotation.sin_cos();
+
+        // Corners of the image, as seen on the frame
+        let corners: Vec<Vector> = [(-0.5, -0.5), (0.5, -0.5), (0.5, 0.5), (-0.5, 0.5)].iter()
+            .map(|(i, j)| {
+                let (dx, dy) = (i * self.width, j * self.height);
+                frame.to_pixels(center + Vector::new(dx * cos - dy * sin, dx * sin + dy * cos))
+            })
+            .collect();
+
+        // Pixels covered by the image
+        let clamp = |value: f64, limit: u32| value.clamp(0.0, limit as f64) as u32;
+        let x0 = clamp(corners.iter().map(|c| c.x).fold(f64::INFINITY, f64::min).floor() - 1.0, frame.image.width());
+        let y0 = clamp(corners.iter().map(|c| c.y).fold(f64::INFINITY, f64::min).floor() - 1.0, frame.image.height());
+        let x1 = clamp(corners.iter().map(|c| c.x).fold(f64::NEG_INFINITY, f64::max).ceil() + 1.0, frame.image.width());
+        let y1 = clamp(corners.iter().map(|c| c.y).fold(f64::NEG_INFINITY, f64::max).ceil() + 1.0, frame.image.height());
+
+        let (w, h) = (self.pixels.width() as f64, self.pixels.height() as f64);
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                // Position of this pixel's center within the (unrotated) image
+                let offset = frame.to_world(Vector::new(x as f64 + 0.5, y as f64 + 0.5)) - center;
+                let (dx, dy) = (offset.x * cos + offset.y * sin, offset.y * cos - offset.x * sin);
+                let (u, v) = ((dx / self.width + 0.5) * w, (0.5 - dy / self.height) * h);
+
+                let (color, alpha) = self.sample(u, v);
+
+                if alpha > 0.0 {
+                    frame.add_pixel(x, y, color, alpha * self.opacity);
+                }
+            }
+        }
+    }
+}
+
+impl Animate for Image {
+    fn play(&self, _: f64) -> Box<dyn Artist> {
+        Box::new(self.clone())
+    }
+
+    fn clone_box(&self) -> Box<dyn Animate> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone)]
+/// An animation that fades an image in or out.
+pub struct Fade {
+    /// Image being faded.
+    image: Image,
+
+    /// Are we fading out?
+    out: bool,
+}
+
+impl Fade {
+    /// Construct a new fading animation.
+    pub fn new(image: Image, out: bool) -> Self {
+        Self {
+            image,
+            out,
+        }
+    }
+}
+
+impl Animate for Fade {
+    fn play(&self, progress: f64) -> Box<dyn Artist> {
+        let mut image = self.image.clone();
+        image.opacity *= if self.out { 1.0 - progress } else { progress };
+
+        Box::new(image)
+    }
+
+    fn clone_box(&self) -> Box<dyn Animate> {
+        Box::new(self.clone())
+    }
+}
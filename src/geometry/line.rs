@@ -0,0 +1,29 @@
+//! A straight line segment.
+
+use pyo3::prelude::*;
+
+use crate::{
+    Bezier,
+    Color,
+    Shape,
+    Vector,
+};
+
+#[pyclass(extends=Shape)]
+#[derive(Clone)]
+/// A straight line segment.
+pub struct Line;
+
+#[pymethods]
+impl Line {
+    #[new]
+    #[pyo3(signature=(start, end, color=None, thickness=None))]
+    /// Construct a new line segment from `start` to `end`.
+    pub fn pynew(start: Vector, end: Vector, color: Option<Color>, thickness: Option<f64>) -> (Self, Shape) {
+        let color = color.map(|color| color.rgb());
+
+        let shape = Shape::new(vec![Bezier::new(vec![start, end], Vector::zero(), color, thickness)], Vector::zero());
+
+        (Self {}, shape)
+    }
+}
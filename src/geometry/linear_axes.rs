@@ -1,29 +1,35 @@
 //! Coordinate axes.
 
+use image::Rgb;
+
 use crate::{
+    bounds,
     Animate,
     Animation,
+    Arrow,
+    ArrowHead,
     Artist,
     Bezier,
+    Color,
+    ColorSpace,
+    Frame,
     TracedShape,
     Vector,
 };
 
-use image::RgbImage;
-
 use pyo3::prelude::*;
 
-/// Color for major gridlines.
-const MAJOR_COLOR: [u8; 3] = [255, 255, 255];
+/// Thickness for major gridlines, as a fraction of the grid spacing (with world units).
+const MAJOR_THICKNESS: f64 = 0.04;
 
-/// Color for minor gridlines.
-const MINOR_COLOR: [u8; 3] = [160, 160, 160];
+/// Thickness for minor gridlines, as a fraction of the grid spacing (with world units).
+const MINOR_THICKNESS: f64 = 0.02;
 
-/// Thickness for major gridlines.
-const MAJOR_THICKNESS: i32 = 4;
+/// Thickness for major gridlines, in pixels (without world units).
+const MAJOR_PIXELS: f64 = 4.0;
 
-/// Thickness for minor gridlines.
-const MINOR_THICKNESS: i32 = 2;
+/// Thickness for minor gridlines, in pixels (without world units).
+const MINOR_PIXELS: f64 = 2.0;
 
 /// Progress multiplier for each successive minor gridline (smaller is denser).
 const MULT: f64 = 0.25;
@@ -43,66 +49,91 @@ pub struct LinearAxes {
 
     /// Y major gridline.
     pub y_major: Bezier,
+
+    /// Color of major gridlines, or `None` for the theme's major axis color.
+    pub major_color: Option<Rgb<u8>>,
+
+    /// Color of minor gridlines, or `None` for the theme's minor axis color.
+    pub minor_color: Option<Rgb<u8>>,
+
+    #[pyo3(get)]
+    /// Origin of these axes.
+    pub origin: Vector,
+
+    #[pyo3(get)]
+    /// Spacing between gridlines, which is one unit along each axis.
+    pub spacing: f64,
 }
 
 #[pymethods]
 impl LinearAxes {
     #[new]
+    #[pyo3(signature=(origin, spacing, x_count, y_count, major_color=None, minor_color=None))]
     /// Create a set of coordinate axes with a specified origin, line spacing, and line count.
-    pub fn new(origin: Vector, spacing: f64, x_count: (usize, usize), y_count: (usize, usize)) -> Self {
+    pub fn new(origin: Vector, spacing: f64, x_count: (usize, usize), y_count: (usize, usize), major_color: Option<Color>, minor_color: Option<Color>) -> Self {
+        let major_color = major_color.map(|color| color.rgb());
+        let minor_color = minor_color.map(|color| color.rgb());
+
         // Minimum/maximum X and Y values
-        let mut x_min = origin.x;
-        let mut x_max = origin.x;
-        let mut y_min = origin.y;
-        let mut y_max = origin.y;
-
-        // Current X value
-        let mut x = origin.x;
-
-        // X values to draw minor gridlines at
-        let mut x_vals = Vec::new();
-
-        // Draw positive X lines
-        for _ in 0..x_count.1 {
-            x += spacing;
-            x_vals.push(x);
-            x_max = x_max.max(x);
-        }
+        let x_range = (origin.x - spacing * x_count.0 as f64, origin.x + spacing * x_count.1 as f64);
+        let y_range = (origin.y - spacing * y_count.0 as f64, origin.y + spacing * y_count.1 as f64);
 
-        // Draw negative X lines
-        x = origin.x;
-        for _ in 0..x_count.0 {
-            x -= spacing;
-            x_vals.push(x);
-            x_min = x_min.min(x);
-        }
+        Self::build(origin, spacing, x_range, y_range, major_color, minor_color)
+    }
 
-        // Sort X values away from origin
-        x_vals.sort_by(|a, b| (a - origin.x).abs().partial_cmp(&(b - origin.x).abs()).unwrap());
+    #[pyo3(signature=(vector, color=None, thickness=None, head=ArrowHead::Triangle, head_size=None))]
+    /// Construct an arrow from the origin of these axes to a vector, measured in units
+    /// of these axes (one unit per gridline).
+    pub fn arrow(&self, py: Python<'_>, vector: Vector, color: Option<Color>, thickness: Option<f64>, head: ArrowHead, head_size: Option<f64>) -> PyResult<Py<Arrow>> {
+        let tip = self.origin + vector * self.spacing;
 
-        // Current Y value
-        let mut y = origin.y;
+        Py::new(py, Arrow::pynew(self.origin, tip, color, thickness, head, head_size, false, Vec::new()))
+    }
 
-        // Y values to draw minor gridlines at
-        let mut y_vals = Vec::new();
+    /// Find the smallest box `(min, max)` containing these axes' major gridlines.
+    pub fn bounding_box(&self) -> (Vector, Vector) {
+        let (x_min, x_max) = self.x_major.bounding_box();
+        let (y_min, y_max) = self.y_major.bounding_box();
 
-        // Draw positive Y lines
-        for _ in 0..y_count.1 {
-            y += spacing;
-            y_vals.push(y);
-            y_max = y_max.max(y);
-        }
+        bounds([x_min, x_max, y_min, y_max])
+    }
 
-        // Draw negative Y lines
-        y = origin.y;
-        for _ in 0..y_count.0 {
-            y -= spacing;
-            y_vals.push(y);
-            y_min = y_min.min(y);
-        }
+    #[pyo3(signature=(other, space=ColorSpace::Oklab))]
+    /// Interpolate these axes with another set, moving the origin and changing the spacing
+    /// and extent of the gridlines, and blending colors in a given color space.
+    pub fn into(&self, other: LinearAxes, space: ColorSpace) -> Animation {
+        InterpolateLinearAxes::new(self.clone(), other, space).animate()
+    }
 
-        // Sort Y values away from origin
-        y_vals.sort_by(|a, b| (a - origin.y).abs().partial_cmp(&(b - origin.y).abs()).unwrap());
+    #[getter]
+    /// Display these axes on screen.
+    pub fn get_display(&self) -> Animation {
+        Animate::animate(self)
+    }
+
+    #[getter]
+    /// Construct a tracing animation.
+    pub fn get_trace(&self) -> Animation {
+        TraceLinearAxes::new(self.clone(), false).animate()
+    }
+
+    #[getter]
+    /// Construct an untracing animation.
+    pub fn get_untrace(&self) -> Animation {
+        TraceLinearAxes::new(self.clone(), true).animate()
+    }
+}
+
+impl LinearAxes {
+    /// Construct a set of coordinate axes with a specified origin and line spacing, covering
+    /// a range of X and Y values (world units).
+    pub fn build(origin: Vector, spacing: f64, x_range: (f64, f64), y_range: (f64, f64), major_color: Option<Rgb<u8>>, minor_color: Option<Rgb<u8>>) -> Self {
+        let (x_min, x_max) = x_range;
+        let (y_min, y_max) = y_range;
+
+        // X and Y values to draw minor gridlines at, sorted away from the origin
+        let x_vals = gridlines(origin.x, spacing, x_range);
+        let y_vals = gridlines(origin.y, spacing, y_range);
 
         // Resultant Bezier curves
         let mut x_minors = Vec::new();
@@ -113,8 +144,8 @@ impl LinearAxes {
             let gridline = Bezier::new(
                 vec![Vector::new(x_val, y_min), Vector::new(x_val, y_max)],
                 Vector::zero(),
-                MINOR_COLOR,
-                MINOR_THICKNESS,
+                minor_color,
+                None,
             );
 
             x_minors.push(gridline);
@@ -125,8 +156,8 @@ impl LinearAxes {
             let gridline = Bezier::new(
                 vec![Vector::new(x_min, y_val), Vector::new(x_max, y_val)],
                 Vector::zero(),
-                MINOR_COLOR,
-                MINOR_THICKNESS,
+                minor_color,
+                None,
             );
 
             y_minors.push(gridline);
@@ -136,14 +167,14 @@ impl LinearAxes {
         let x_major = Bezier::new(
             vec![Vector::new(origin.x, y_min), Vector::new(origin.x, y_max)],
             Vector::zero(),
-            MAJOR_COLOR,
-            MAJOR_THICKNESS,
+            major_color,
+            None,
         );
         let y_major = Bezier::new(
             vec![Vector::new(x_min, origin.y), Vector::new(x_max, origin.y)],
             Vector::zero(),
-            MAJOR_COLOR,
-            MAJOR_THICKNESS,
+            major_color,
+            None,
         );
 
         Self {
@@ -151,43 +182,87 @@ impl LinearAxes {
             y_minors,
             x_major,
             y_major,
+            major_color,
+            minor_color,
+            origin,
+            spacing,
         }
     }
 
-    #[getter]
-    /// Display these axes on screen.
-    pub fn get_display(&self) -> Animation {
-        Animate::animate(self)
+
+    /// Find the ranges of X and Y values (world units) covered by these axes.
+    pub fn ranges(&self) -> ((f64, f64), (f64, f64)) {
+        let (x_min, x_max) = self.y_major.hull_box();
+        let (y_min, y_max) = self.x_major.hull_box();
+
+        ((x_min.x, x_max.x), (y_min.y, y_max.y))
     }
 
-    #[getter]
-    /// Construct a tracing animation.
-    pub fn get_trace(&self) -> Animation {
-        TraceLinearAxes::new(self.clone(), false).animate()
+    /// Give these axes the theme's axis colors, unless they specify their own, and
+    /// gridline thicknesses to suit a frame.
+    /// 
+    /// With world units, gridlines are as thick as a fraction of the grid spacing.
+    /// Otherwise, they are a fixed number of pixels thick.
+    pub fn themed(&self, frame: &Frame) -> Self {
+        let major = Some (self.major_color.unwrap_or(frame.theme.major_axis));
+        let minor = Some (self.minor_color.unwrap_or(frame.theme.minor_axis));
+
+        let (major_thickness, minor_thickness) = match frame.pixel {
+            Some (_) => (MAJOR_THICKNESS * self.spacing, MINOR_THICKNESS * self.spacing),
+            None => (MAJOR_PIXELS, MINOR_PIXELS),
+        };
+
+        let mut axes = self.clone();
+        for curve in axes.x_minors.iter_mut().chain(axes.y_minors.iter_mut()) {
+            curve.color = minor;
+            curve.stroke.thickness = Some (minor_thickness);
+        }
+        axes.x_major.color = major;
+        axes.x_major.stroke.thickness = Some (major_thickness);
+        axes.y_major.color = major;
+        axes.y_major.stroke.thickness = Some (major_thickness);
+
+        axes
     }
+}
 
-    #[getter]
-    /// Construct an untracing animation.
-    pub fn get_untrace(&self) -> Animation {
-        TraceLinearAxes::new(self.clone(), true).animate()
+/// Find the values at which to draw minor gridlines, spaced from an origin value within a
+/// range, sorted away from the origin.
+fn gridlines(origin: f64, spacing: f64, range: (f64, f64)) -> Vec<f64> {
+    if spacing <= 0.0 {
+        return Vec::new();
     }
+
+    // Gridlines at the ends of the range are kept, despite rounding
+    let slack = 1E-9;
+    let above = ((range.1 - origin) / spacing + slack).floor().max(0.0) as usize;
+    let below = ((origin - range.0) / spacing + slack).floor().max(0.0) as usize;
+
+    let mut values: Vec<f64> = (1..=above).map(|k| origin + spacing * k as f64)
+        .chain((1..=below).map(|k| origin - spacing * k as f64))
+        .collect();
+    values.sort_by(|a, b| (a - origin).abs().total_cmp(&(b - origin).abs()));
+
+    values
 }
 
 impl Artist for LinearAxes {
-    fn draw(&self, location: Vector, image: &mut RgbImage) {
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        let axes = self.themed(frame);
+
         // Render X minor gridlines
-        for curve in &self.x_minors {
-            curve.draw(location, image);
+        for curve in &axes.x_minors {
+            curve.draw(location, frame);
         }
 
         // Render Y minor gridlines
-        for curve in &self.y_minors {
-            curve.draw(location, image);
+        for curve in &axes.y_minors {
+            curve.draw(location, frame);
         }
 
         // Render major gridlines
-        self.x_major.draw(location, image);
-        self.y_major.draw(location, image);
+        axes.x_major.draw(location, frame);
+        axes.y_major.draw(location, frame);
     }
 }
 
@@ -255,41 +330,113 @@ impl TracedLinearAxes {
 }
 
 impl Artist for TracedLinearAxes {
-    fn draw(&self, location: Vector, image: &mut RgbImage) {
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        let axes = self.linear_axes.themed(frame);
+
         // X minor gridlines
-        for (i, x_minor) in self.linear_axes.x_minors.iter().enumerate() {
+        for (i, x_minor) in axes.x_minors.iter().enumerate() {
             let x_minor = TracedShape::new(
                 x_minor.get_shape(),
                 self.progress.powf(i as f64 * MULT + (1.0 + MULT)),
                 self.untrace,
             );
-            x_minor.draw(location, image);
+            x_minor.draw(location, frame);
         }
 
         // Y minor gridlines
-        for (j, y_minor) in self.linear_axes.y_minors.iter().enumerate() {
+        for (j, y_minor) in axes.y_minors.iter().enumerate() {
             let y_minor = TracedShape::new(
                 y_minor.get_shape(),
                 self.progress.powf(j as f64 * MULT + (1.0 + MULT)),
                 self.untrace,
             );
-            y_minor.draw(location, image);
+            y_minor.draw(location, frame);
         }
 
         // X major gridline
         let x_major = TracedShape::new(
-            self.linear_axes.x_major.get_shape(),
+            axes.x_major.get_shape(),
             self.progress,
             self.untrace,
         );
-        x_major.draw(location, image);
+        x_major.draw(location, frame);
 
         // Y major gridline
         let y_major = TracedShape::new(
-            self.linear_axes.y_major.get_shape(),
+            axes.y_major.get_shape(),
             self.progress,
             self.untrace,
         );
-        y_major.draw(location, image);
+        y_major.draw(location, frame);
+    }
+}
+
+#[derive(Clone)]
+/// An interpolation animation, where one set of linear axes smoothly becomes another.
+pub struct InterpolateLinearAxes {
+    one: LinearAxes,
+    two: LinearAxes,
+
+    /// Color space in which colors are blended.
+    space: ColorSpace,
+}
+
+impl InterpolateLinearAxes {
+    /// Construct a new interpolation, blending colors in a given color space.
+    pub fn new(one: LinearAxes, two: LinearAxes, space: ColorSpace) -> Self {
+        Self {
+            one,
+            two,
+            space,
+        }
+    }
+}
+
+impl Animate for InterpolateLinearAxes {
+    fn play(&self, progress: f64) -> Box<dyn Artist> {
+        Box::new(InterpolatedLinearAxes {
+            one: self.one.clone(),
+            two: self.two.clone(),
+            progress,
+            space: self.space,
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn Animate> {
+        Box::new(self.clone())
+    }
+}
+
+/// A set of linear axes partway between two others.
+pub struct InterpolatedLinearAxes {
+    one: LinearAxes,
+    two: LinearAxes,
+    progress: f64,
+    space: ColorSpace,
+}
+
+impl Artist for InterpolatedLinearAxes {
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        let p = self.progress;
+        let mix = |a: f64, b: f64| a * (1.0 - p) + b * p;
+        let mix_range = |a: (f64, f64), b: (f64, f64)| (mix(a.0, b.0), mix(a.1, b.1));
+
+        // Colors are blended once the theme has given both sets of axes their colors
+        let theme = &frame.theme;
+        let major = self.space.lerp(self.one.major_color.unwrap_or(theme.major_axis), self.two.major_color.unwrap_or(theme.major_axis), p);
+        let minor = self.space.lerp(self.one.minor_color.unwrap_or(theme.minor_axis), self.two.minor_color.unwrap_or(theme.minor_axis), p);
+
+        let ((x_one, y_one), (x_two, y_two)) = (self.one.ranges(), self.two.ranges());
+
+        let axes = LinearAxes::build(
+            self.one.origin.lerp(self.two.origin, p),
+            mix(self.one.spacing, self.two.spacing),
+            mix_range(x_one, x_two),
+            mix_range(y_one, y_two),
+            Some (major),
+            Some (minor),
+        );
+
+        axes.draw(location, frame);
     }
-}
\ No newline at end of file
+}
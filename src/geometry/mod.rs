@@ -1,19 +1,62 @@
 //! Geometry abstractions for Beaver Studio.
 
+mod annulus;
+mod arc;
+mod arrow;
 mod bezier;
+mod boolean;
 mod circle;
+mod ellipse;
+mod image;
+mod line;
 mod linear_axes;
 mod parametric;
 mod polygon;
+mod polyline;
 mod rectangle;
+mod regular_polygon;
+mod rounded_rectangle;
+mod sector;
 mod shape;
+mod spline;
+mod star;
 mod vector;
 
-pub use bezier::Bezier;
+pub use annulus::Annulus;
+pub use arc::{
+    arc_curves,
+    CircularArc,
+};
+pub use arrow::{
+    Arrow,
+    ArrowHead,
+    Arrowheads,
+};
+pub use bezier::{
+    bounds,
+    distinct,
+    Bezier,
+};
+pub use boolean::{
+    combine,
+    BooleanOp,
+};
 pub use circle::Circle;
+pub use ellipse::Ellipse;
+pub use image::Image;
+pub use line::Line;
 pub use linear_axes::LinearAxes;
 pub use parametric::Parametric;
 pub use polygon::Polygon;
+pub use polyline::Polyline;
 pub use rectangle::Rectangle;
+pub use regular_polygon::{
+    closed_path,
+    RegularPolygon,
+};
+pub use rounded_rectangle::RoundedRectangle;
+pub use sector::Sector;
 pub use shape::Shape;
+pub use spline::Spline;
+pub use star::Star;
 pub use vector::Vector;
\ No newline at end of file
@@ -1,74 +1,276 @@
 //! A 2D parametric curve.
 
-use image::{
-    Rgb,
-    RgbImage,
-};
+use std::sync::Arc;
+
+use image::Rgb;
 
 use pyo3::{
+    exceptions::PyValueError,
     prelude::*,
-    types::PyFunction,
 };
 
 use crate::{
-    add_pixel,
+    bounds,
     Animate,
     Animation,
     Artist,
-    Bresenham,
-    Brush,
-    STEP,
+    Color,
+    ColorSpace,
+    Frame,
+    LineCap,
+    LineJoin,
+    Paint,
+    Shape,
+    Stroke,
+    Style,
     Vector,
 };
 
+/// Largest distance (pixels of the output video) a sampled curve strays from the true curve, by default.
+const TOLERANCE: f64 = 0.1;
+
+/// Number of equal intervals a curve is first sampled at.
+const INTERVALS: usize = 64;
+
+/// Most times each interval is halved while sampling a curve.
+const MAX_DEPTH: usize = 12;
+
+#[derive(Clone)]
+/// Function giving the position of a parametric curve at each time.
+enum Function {
+    /// A pair of Python callables giving the X and Y positions, which take (and return)
+    /// numpy arrays of times if vectorized.
+    Python {
+        x: Arc<Py<PyAny>>,
+        y: Arc<Py<PyAny>>,
+        vectorized: bool,
+    },
+
+    /// A Rust closure giving the position.
+    Rust (Arc<dyn Fn(f64) -> Vector + Send + Sync>),
+}
+
+impl Function {
+    /// Evaluate this function at a series of times.
+    fn evaluate(&self, times: &[f64]) -> PyResult<Vec<Vector>> {
+        let points: Vec<Vector> = match self {
+            Self::Python { x, y, vectorized } => Python::with_gil(|py| -> PyResult<Vec<Vector>> {
+                let xs = call(py, x, times, *vectorized)?;
+                let ys = call(py, y, times, *vectorized)?;
+
+                Ok(xs.into_iter().zip(ys).map(|(x, y)| Vector::new(x, y)).collect())
+            })?,
+            Self::Rust (function) => times.iter().map(|t| function(*t)).collect(),
+        };
+
+        if let Some ((t, _)) = times.iter().zip(&points).find(|(_, point)| !point.x.is_finite() || !point.y.is_finite()) {
+            return Err(PyValueError::new_err(format!("parametric function is not finite at time {}", t)));
+        }
+
+        Ok(points)
+    }
+}
+
+/// Call a Python function of time at a series of times, all at once if it is vectorized.
+fn call(py: Python<'_>, function: &Py<PyAny>, times: &[f64], vectorized: bool) -> PyResult<Vec<f64>> {
+    if !vectorized {
+        return times.iter().map(|t| function.call1(py, (*t,))?.extract(py)).collect();
+    }
+
+    // Constant functions give a single value, which is spread over every time
+    let numpy = py.import("numpy")?;
+    let times = numpy.call_method1("asarray", (times.to_vec(),))?;
+    let values = numpy.call_method1("asarray", (function.call1(py, (&times,))?, "float64"))?;
+
+    numpy.call_method1("broadcast_to", (values, times.getattr("shape")?))?
+        .call_method0("tolist")?
+        .extract()
+}
+
 #[pyclass]
 #[derive(Clone)]
 /// A 2D parametric curve.
+/// 
+/// The curve keeps its function, and is sampled adaptively (more densely where it bends)
+/// whenever it is built or its times or tolerance change.  Curves without a tolerance of
+/// their own are sampled again when rendered, to the default tolerance in the video's pixels.
 pub struct Parametric {
-    /// Points along this parametric curve, as well as their time values.
+    /// Function giving the position of this curve at each time.
+    function: Function,
+
+    /// Time bounds (min, max).
+    times: (f64, f64),
+
+    /// Offset of this curve.
+    origin: Vector,
+
+    /// Largest distance (world units) the sampled curve strays from the true curve, or `None`
+    /// for the default tolerance in pixels.
+    tolerance: Option<f64>,
+
+    /// Size (world units) of a pixel the default tolerance is measured in, which is only known
+    /// once the curve is rendered.
+    pixel: f64,
+
+    /// Points along this parametric curve, as well as their time values (between 0 and 1).
     pub points: Vec<(Vector, f64)>,
 
-    /// Color of curve.
-    pub color: Rgb<u8>,
+    /// Color of curve, or `None` for the theme's foreground color.
+    pub color: Option<Rgb<u8>>,
+
+    /// Stroke style.
+    pub stroke: Stroke,
 
-    /// Curve brush.
-    pub brush: Brush,
+    /// Paint filling the inside of this curve, if any.
+    pub fill: Option<Paint>,
 }
 
 #[pymethods]
 impl Parametric {
     #[new]
+    #[pyo3(signature=(x_func, y_func, times, origin, color=None, thickness=None, tolerance=None, vectorized=false))]
     /// Construct a new parametric function.
-    pub fn new(x_func: Py<PyFunction>, y_func: Py<PyFunction>, times: (f64, f64), origin: Vector, color: [u8; 3], thickness: i32) -> Self {
-        // Build collection of points to interpolate between
-        let mut t = times.0;
-        let mut points = Vec::new();
+    /// 
+    /// If `vectorized`, the functions are called once with a numpy array of times, rather
+    /// than once for each time.
+    #[allow(clippy::too_many_arguments, reason = "style and sampling options are Python keyword arguments")]
+    pub fn new(x_func: Py<PyAny>, y_func: Py<PyAny>, times: (f64, f64), origin: Vector, color: Option<Color>, thickness: Option<f64>, tolerance: Option<f64>, vectorized: bool) -> PyResult<Self> {
+        let function = Function::Python {
+            x: Arc::new(x_func),
+            y: Arc::new(y_func),
+            vectorized,
+        };
+
+        Self::build(function, times, origin, color.map(|color| color.rgb()), Stroke::new(thickness), tolerance)
+    }
 
-        while t <= times.1 {
-            // Step along the curve
-            t += STEP;
+    #[getter]
+    /// Time bounds (min, max) of this curve.
+    pub fn get_times(&self) -> (f64, f64) {
+        self.times
+    }
 
-            // Scale to between 0 and 1
-            let t_fixed = (t - times.0) / (times.1 - times.0);
+    #[setter]
+    /// Set the time bounds (min, max) of this curve, sampling it again.
+    pub fn set_times(&mut self, times: (f64, f64)) -> PyResult<()> {
+        check(times, self.tolerance)?;
+        let previous = std::mem::replace(&mut self.times, times);
 
-            // Get X and Y position
-            let pos = Python::with_gil(|py| {
-                // TODO this is sloppy
-                let x: f64 = x_func.call(py, (t,), None).unwrap().extract(py).unwrap();
-                let y: f64 = y_func.call(py, (t,), None).unwrap().extract(py).unwrap();
+        // Keep the previous times if the function fails at the new ones
+        self.resample().inspect_err(|_| self.times = previous)
+    }
 
-                Vector::new(x, y)
-            });
+    #[getter]
+    /// Offset of this curve.
+    pub fn get_origin(&self) -> Vector {
+        self.origin
+    }
 
-            // Save this point
-            points.push((origin + pos, t_fixed));
+    #[setter]
+    /// Set the offset of this curve.
+    pub fn set_origin(&mut self, origin: Vector) {
+        let offset = origin - self.origin;
+        for (point, _) in &mut self.points {
+            *point = *point + offset;
         }
 
-        Self {
-            points,
-            color: Rgb (color),
-            brush: Brush::new(thickness),
+        self.origin = origin;
+    }
+
+    #[getter]
+    /// Largest distance (world units) the sampled curve strays from the true curve, or `None`
+    /// for the default tolerance in pixels.
+    pub fn get_tolerance(&self) -> Option<f64> {
+        self.tolerance
+    }
+
+    #[setter]
+    /// Set the largest distance (world units) the sampled curve strays from the true curve
+    /// (`None` for the default tolerance in pixels), sampling it again.
+    pub fn set_tolerance(&mut self, tolerance: Option<f64>) -> PyResult<()> {
+        check(self.times, tolerance)?;
+        let previous = std::mem::replace(&mut self.tolerance, tolerance);
+
+        // Keep the previous tolerance if the function fails while sampling
+        self.resample().inspect_err(|_| self.tolerance = previous)
+    }
+
+    #[getter]
+    /// Shape of the ends of this curve.
+    pub fn get_cap(&self) -> &'static str {
+        self.stroke.cap.name()
+    }
+
+    #[setter]
+    /// Set the shape of the ends of this curve (`"butt"`, `"round"`, or `"square"`).
+    pub fn set_cap(&mut self, cap: LineCap) {
+        self.stroke.cap = cap;
+    }
+
+    #[getter]
+    /// Shape of the corners of this curve.
+    pub fn get_join(&self) -> &'static str {
+        self.stroke.join.name()
+    }
+
+    #[setter]
+    /// Set the shape of the corners of this curve (`"miter"`, `"round"`, or `"bevel"`).
+    pub fn set_join(&mut self, join: LineJoin) {
+        self.stroke.join = join;
+    }
+
+    #[getter]
+    /// Paint of this curve, if it is not a solid color.
+    pub fn get_paint(&self) -> Option<Paint> {
+        self.stroke.paint.clone()
+    }
+
+    #[setter]
+    /// Set the paint of this curve (`None` for its solid color).
+    pub fn set_paint(&mut self, paint: Option<Paint>) {
+        self.stroke.paint = paint;
+    }
+
+    #[getter]
+    /// Paint filling the inside of this curve, if any.
+    pub fn get_fill(&self) -> Option<Paint> {
+        self.fill.clone()
+    }
+
+    #[setter]
+    /// Set the paint filling the inside of this curve (`None` for no fill).
+    pub fn set_fill(&mut self, fill: Option<Paint>) -> PyResult<()> {
+        if fill.as_ref().is_some_and(Paint::is_along) {
+            return Err(PyValueError::new_err("fills cannot be painted along a stroke"));
         }
+
+        self.fill = fill;
+
+        Ok(())
+    }
+
+    #[getter]
+    /// Dash pattern of this curve, as alternating dash and gap lengths (world units).
+    pub fn get_dash(&self) -> Vec<f64> {
+        self.stroke.dash.clone()
+    }
+
+    #[setter]
+    /// Set the dash pattern of this curve (an empty list gives a solid curve).
+    pub fn set_dash(&mut self, dash: Vec<f64>) -> PyResult<()> {
+        self.stroke.set_dash(dash)
+    }
+
+    #[getter]
+    /// Distance (world units) into the dash pattern at which this curve begins.
+    pub fn get_dash_offset(&self) -> f64 {
+        self.stroke.dash_offset
+    }
+
+    #[setter]
+    /// Set the distance (world units) into the dash pattern at which this curve begins.
+    pub fn set_dash_offset(&mut self, offset: f64) {
+        self.stroke.dash_offset = offset;
     }
 
     #[getter]
@@ -88,38 +290,261 @@ impl Parametric {
     pub fn get_untrace(&self) -> Animation {
         TraceParametric::new(self.clone(), true).animate()
     }
+
+    /// Construct an animation that moves the dashes of this curve forward by some distance (world units).
+    pub fn march(&self, distance: f64) -> Animation {
+        MarchParametric::new(self.clone(), distance).animate()
+    }
+
+    #[pyo3(signature=(other, space=ColorSpace::Oklab))]
+    /// Interpolate this curve with another parametric curve or a shape, blending colors in
+    /// a given color space.
+    /// 
+    /// Points of the two curves are paired by their (normalized) time.
+    pub fn into(&self, other: &Bound<'_, PyAny>, space: ColorSpace) -> PyResult<Animation> {
+        let other = match other.extract::<Parametric>() {
+            Ok (curve) => curve,
+            Err (_) => Parametric::from_shape(&other.extract::<Shape>()?)?,
+        };
+
+        Ok(InterpolateParametric::new(self.clone(), other, space).animate())
+    }
+
+    /// Find the smallest box `(min, max)` containing this curve.
+    pub fn bounding_box(&self) -> (Vector, Vector) {
+        bounds(self.points.iter().map(|(point, _)| *point))
+    }
+
+    /// Find the point on this curve nearest to a given point.
+    pub fn nearest_point(&self, point: Vector) -> Vector {
+        let nearest = |a: Vector, b: Vector| {
+            // Nearest point on the segment from `a` to `b`
+            let along = b - a;
+            let t = if along.norm() > 0.0 { ((point - a).dot(along) / along.dot(along)).clamp(0.0, 1.0) } else { 0.0 };
+
+            a + along * t
+        };
+
+        self.points.windows(2)
+            .map(|pair| nearest(pair[0].0, pair[1].0))
+            .chain(self.points.first().map(|(point, _)| *point))
+            .min_by(|a, b| (*a - point).norm().total_cmp(&(*b - point).norm()))
+            .unwrap_or(point)
+    }
 }
 
-impl Artist for Parametric {
-    fn draw(&self, location: Vector, image: &mut RgbImage) {
-        // Interpolation (Bresenham's line algorithm)
-        for i in 0..(self.points.len() - 1) {
-            // Two points to draw between
-            let this_point = location + self.points[i].0;
-            let next_point = location + self.points[i + 1].0;
-
-            // Convert points to integers
-            let (x0, y0) = this_point.to_pixels(image.width(), image.height());
-            let (x1, y1) = next_point.to_pixels(image.width(), image.height());
-
-            // Construct Bresenham line
-            let line = Bresenham::new(x0, y0, x1, y1).points;
-
-            // Draw first point
-            for (x, y) in line {
-                for (i, j, strength) in &self.brush.points {
-                    add_pixel(image, (x as i32 + i) as u32, (y as i32 + j) as u32, self.color, *strength);
+impl Parametric {
+    /// Construct a new parametric curve from a function, and sample it.
+    fn build(function: Function, times: (f64, f64), origin: Vector, color: Option<Rgb<u8>>, stroke: Stroke, tolerance: Option<f64>) -> PyResult<Self> {
+        check(times, tolerance)?;
+
+        let mut curve = Self {
+            function,
+            times,
+            origin,
+            tolerance,
+            pixel: 1.0,
+            points: Vec::new(),
+            color,
+            stroke,
+            fill: None,
+        };
+        curve.resample()?;
+
+        Ok(curve)
+    }
+
+    /// Construct a parametric curve following the outline of a shape (over times from 0 to 1),
+    /// in its style.
+    pub fn from_shape(shape: &Shape) -> PyResult<Self> {
+        let curves = shape.placed();
+        let function = move |t: f64| {
+            if curves.is_empty() {
+                return Vector::zero();
+            }
+
+            // Which curve are we on, and how far along it?
+            let position = t * curves.len() as f64;
+            let i = (position.floor() as usize).min(curves.len() - 1);
+
+            curves[i].trace(position - i as f64)
+        };
+
+        let mut curve = Self::build(Function::Rust (Arc::new(function)), (0.0, 1.0), Vector::zero(), shape.color, shape.stroke.clone(), None)?;
+        curve.fill = shape.fill.clone();
+
+        Ok(curve)
+    }
+
+    /// Sample the function of this curve between its time bounds, halving each interval
+    /// wherever the curve strays from a straight line by more than the tolerance.
+    /// 
+    /// All samples added at once are evaluated together, so vectorized functions are
+    /// called only a few times.
+    pub fn resample(&mut self) -> PyResult<()> {
+        let tolerance = self.tolerance.unwrap_or(TOLERANCE * self.pixel);
+        let (t0, t1) = self.times;
+        let times: Vec<f64> = (0..=INTERVALS).map(|k| t0 + (t1 - t0) * k as f64 / INTERVALS as f64).collect();
+        let mut samples: Vec<(f64, Vector)> = times.iter().copied().zip(self.function.evaluate(&times)?).collect();
+
+        // Which intervals (between consecutive samples) might still need halving?
+        let mut pending = vec![true; INTERVALS];
+
+        for _ in 0..MAX_DEPTH {
+            let middles: Vec<f64> = samples.windows(2).zip(&pending)
+                .filter(|(_, pending)| **pending)
+                .map(|(pair, _)| 0.5 * (pair[0].0 + pair[1].0))
+                .collect();
+
+            if middles.is_empty() {
+                break;
+            }
+
+            let mut values = middles.iter().copied().zip(self.function.evaluate(&middles)?);
+            let mut next = Vec::with_capacity(samples.len() + middles.len());
+            let mut next_pending = Vec::with_capacity(pending.len() + middles.len());
+
+            for (pair, pending) in samples.windows(2).zip(&pending) {
+                next.push(pair[0]);
+
+                // Only pending intervals were given a middle
+                let middle = if *pending { values.next() } else { None };
+
+                match middle {
+                    Some ((t, point)) if (point - (pair[0].1 + pair[1].1) * 0.5).norm() > tolerance => {
+                        next.push((t, point));
+                        next_pending.extend([true, true]);
+                    },
+                    _ => next_pending.push(false),
                 }
             }
+            next.extend(samples.last().copied());
+
+            samples = next;
+            pending = next_pending;
+        }
+
+        self.points = samples.into_iter()
+            .map(|(t, point)| (self.origin + point, (t - t0) / (t1 - t0)))
+            .collect();
+
+        Ok(())
+    }
+
+    /// Find the point of this curve at a normalized time (between 0 and 1), between the
+    /// nearest samples.
+    pub fn at(&self, t: f64) -> Vector {
+        let (first, last) = match (self.points.first(), self.points.last()) {
+            (Some (first), Some (last)) => (first, last),
+            _ => return Vector::zero(),
+        };
+
+        if t <= first.1 {
+            return first.0;
+        }
+        if t >= last.1 {
+            return last.0;
+        }
+
+        // Samples on either side of this time
+        let k = self.points.partition_point(|(_, time)| *time < t);
+        let ((a, ta), (b, tb)) = (self.points[k - 1], self.points[k]);
+
+        if tb > ta { a.lerp(b, (t - ta) / (tb - ta)) } else { b }
+    }
+
+    /// Compute the arc length of this curve, up to (but not including) the point at a given index.
+    pub fn arc_length(&self, index: usize) -> f64 {
+        self.points[..index.min(self.points.len())].windows(2).map(|pair| (pair[1].0 - pair[0].0).norm()).sum()
+    }
+
+    /// Does this curve end where it begins?
+    pub fn is_closed(&self) -> bool {
+        match (self.points.first(), self.points.last()) {
+            (Some (first), Some (last)) => (first.0 - last.0).norm() < 1E-6,
+            _ => false,
         }
     }
 }
 
+/// Check the time bounds and tolerance of a parametric curve.
+fn check(times: (f64, f64), tolerance: Option<f64>) -> PyResult<()> {
+    if times.1 <= times.0 {
+        return Err(PyValueError::new_err("parametric time bounds must be increasing"));
+    }
+
+    if tolerance.is_some_and(|tolerance| !(tolerance.is_finite() && tolerance > 0.0)) {
+        return Err(PyValueError::new_err("parametric tolerance must be positive"));
+    }
+
+    Ok(())
+}
+
+impl Artist for Parametric {
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        let points: Vec<Vector> = self.points.iter().map(|(point, _)| location + *point).collect();
+        let params: Vec<f64> = self.points.iter().map(|(_, t)| *t).collect();
+
+        if let Some (fill) = &self.fill {
+            frame.fill(std::slice::from_ref(&points), fill, 1.0);
+        }
+
+        frame.stroke(&points, &params, self.is_closed(), &self.stroke, self.color);
+    }
+}
+
 impl Animate for Parametric {
     fn play(&self, _: f64) -> Box<dyn Artist> {
         Box::new(self.clone())
     }
 
+    fn prepare(&mut self, pixel: f64) -> PyResult<()> {
+        // Only the default tolerance depends on the size of a pixel
+        if self.tolerance.is_some() || pixel == self.pixel {
+            return Ok(());
+        }
+
+        self.pixel = pixel;
+        self.resample()
+    }
+
+    fn clone_box(&self) -> Box<dyn Animate> {
+        Box::new(self.clone())
+    }
+}
+
+#[derive(Clone)]
+/// An animation that moves the dashes of a parametric curve along it.
+pub struct MarchParametric {
+    /// Curve whose dashes are moved.
+    curve: Parametric,
+
+    /// Distance (world units) the dashes move.
+    distance: f64,
+}
+
+impl MarchParametric {
+    /// Construct a new marching animation.
+    pub fn new(curve: Parametric, distance: f64) -> Self {
+        Self {
+            curve,
+            distance,
+        }
+    }
+}
+
+impl Animate for MarchParametric {
+    fn play(&self, progress: f64) -> Box<dyn Artist> {
+        let mut curve = self.curve.clone();
+        curve.stroke.dash_offset -= self.distance * progress;
+
+        Box::new(curve)
+    }
+
+    fn prepare(&mut self, pixel: f64) -> PyResult<()> {
+        self.curve.prepare(pixel)
+    }
+
     fn clone_box(&self) -> Box<dyn Animate> {
         Box::new(self.clone())
     }
@@ -150,6 +575,10 @@ impl Animate for TraceParametric {
         Box::new(TracedParametric::new(self.curve.clone(), progress, self.untrace))
     }
 
+    fn prepare(&mut self, pixel: f64) -> PyResult<()> {
+        self.curve.prepare(pixel)
+    }
+
     fn clone_box(&self) -> Box<dyn Animate> {
         Box::new(self.clone())
     }
@@ -179,39 +608,150 @@ impl TracedParametric {
 }
 
 impl Artist for TracedParametric {
-    fn draw(&self, location: Vector, image: &mut RgbImage) {
-        // Interpolation (Bresenham's line algorithm)
-        for i in 0..(self.curve.points.len() - 1) {
-            // Two points to draw between
-            let this_point = location + self.curve.points[i].0;
-            let next_point = location + self.curve.points[i + 1].0;
-
-            // Convert points to integers
-            let (x0, y0) = this_point.to_pixels(image.width(), image.height());
-            let (x1, y1) = next_point.to_pixels(image.width(), image.height());
-
-            // Construct Bresenham line
-            let line = Bresenham::new(x0, y0, x1, y1).points;
-
-            // Are we past the bounds?
-            if self.untrace {
-                // Before the progress point, skip
-                if self.curve.points[i].1 < self.progress {
-                    continue;
-                }
-            } else {
-                // Past the progress point, stop drawing
-                if self.curve.points[i + 1].1 > self.progress {
-                    break;
-                }
-            }
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        // Points that have been traced
+        let (points, params): (Vec<Vector>, Vec<f64>) = self.curve.points.iter()
+            .filter(|(_, t)| if self.untrace { *t >= self.progress } else { *t <= self.progress })
+            .map(|(point, t)| (location + *point, *t))
+            .unzip();
+
+        // Fill fades in while tracing, and out while untracing
+        if let Some (fill) = &self.curve.fill {
+            let opacity = if self.untrace { 1.0 - self.progress } else { self.progress };
+            let outline: Vec<Vector> = self.curve.points.iter().map(|(point, _)| location + *point).collect();
+            frame.fill(&[outline], fill, opacity);
+        }
 
-            // Draw points
-            for (x, y) in line {
-                for (i, j, strength) in &self.curve.brush.points {
-                    add_pixel(image, (x as i32 + i) as u32, (y as i32 + j) as u32, self.curve.color, *strength);
-                }
-            }
+        // Keep dashes in place when the start of the curve has been untraced
+        let mut stroke = self.curve.stroke.clone();
+        if self.untrace && !stroke.dash.is_empty() {
+            let skipped = self.curve.points.iter().take_while(|(_, t)| *t < self.progress).count();
+            stroke.dash_offset += self.curve.arc_length(skipped);
+        }
+
+        frame.stroke(&points, &params, false, &stroke, self.curve.color);
+    }
+}
+
+#[derive(Clone)]
+/// An interpolation animation, where one parametric curve smoothly becomes another.
+pub struct InterpolateParametric {
+    one: Parametric,
+    two: Parametric,
+
+    /// Color space in which colors are blended.
+    space: ColorSpace,
+}
+
+impl InterpolateParametric {
+    /// Construct a new interpolation, blending colors in a given color space.
+    pub fn new(one: Parametric, two: Parametric, space: ColorSpace) -> Self {
+        Self {
+            one,
+            two,
+            space,
+        }
+    }
+}
+
+impl Animate for InterpolateParametric {
+    fn play(&self, progress: f64) -> Box<dyn Artist> {
+        // Resample both curves at the same times
+        let count = self.one.points.len().max(self.two.points.len()).max(2);
+        let points = (0..count)
+            .map(|k| {
+                let t = k as f64 / (count - 1) as f64;
+                (self.one.at(t).lerp(self.two.at(t), progress), t)
+            })
+            .collect();
+
+        Box::new(InterpolatedParametric {
+            points,
+            one: Style::from(&self.one),
+            two: Style::from(&self.two),
+            closed: self.one.is_closed() && self.two.is_closed(),
+            progress,
+            space: self.space,
+        })
+    }
+
+    fn prepare(&mut self, pixel: f64) -> PyResult<()> {
+        self.one.prepare(pixel)?;
+        self.two.prepare(pixel)
+    }
+
+    fn clone_box(&self) -> Box<dyn Animate> {
+        Box::new(self.clone())
+    }
+}
+
+/// A linear interpolation between two parametric curves.
+pub struct InterpolatedParametric {
+    /// Points partway between those of the two curves, as well as their time values.
+    points: Vec<(Vector, f64)>,
+
+    one: Style,
+    two: Style,
+
+    /// Does this interpolation end where it begins?
+    closed: bool,
+
+    progress: f64,
+    space: ColorSpace,
+}
+
+impl Artist for InterpolatedParametric {
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        let points: Vec<Vector> = self.points.iter().map(|(point, _)| location + *point).collect();
+        let params: Vec<f64> = self.points.iter().map(|(_, t)| *t).collect();
+
+        let (stroke, fill) = self.one.blend(&self.two, frame, self.progress, self.space);
+
+        if let Some ((fill, opacity)) = fill {
+            frame.fill(std::slice::from_ref(&points), &fill, opacity);
+        }
+
+        frame.stroke(&points, &params, self.closed, &stroke, None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increasing_times() {
+        // Straight (so settled early) on the first half, and bending sharply on the second
+        let function = |t: f64| {
+            if t < 0.5 { Vector::new(t, 0.0) } else { Vector::new(t, (40.0 * t).sin()) }
+        };
+        let curve = Parametric::build(Function::Rust (Arc::new(function)), (0.0, 1.0), Vector::zero(), None, Stroke::new(None), Some (1E-3)).unwrap();
+
+        assert!(curve.points.len() > INTERVALS + 1);
+        for pair in curve.points.windows(2) {
+            assert!(pair[1].1 > pair[0].1, "sample times {} and {} are not increasing", pair[0].1, pair[1].1);
+        }
+
+        // Every sample lies on the curve at its time
+        for (point, t) in &curve.points {
+            assert!((*point - function(*t)).norm() < 1E-9);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn default_tolerance_in_pixels() {
+        let circle = |t: f64| Vector::new(t.cos(), t.sin());
+        let mut curve = Parametric::build(Function::Rust (Arc::new(circle)), (0.0, 6.0), Vector::zero(), None, Stroke::new(None), None).unwrap();
+        let coarse = curve.points.len();
+
+        // Smaller pixels need more samples
+        curve.prepare(0.01).unwrap();
+        assert!(curve.points.len() > coarse);
+
+        // Curves with their own tolerance keep their samples
+        curve.set_tolerance(Some (0.1)).unwrap();
+        let fixed = curve.points.clone();
+        curve.prepare(0.001).unwrap();
+        assert_eq!(curve.points, fixed);
+    }
+}
@@ -4,6 +4,7 @@ use pyo3::prelude::*;
 
 use crate::{
     Bezier,
+    Color,
     Shape,
     Vector,
 };
@@ -16,8 +17,11 @@ pub struct Polygon;
 #[pymethods]
 impl Polygon {
     #[new]
+    #[pyo3(signature=(points, center, color=None, thickness=None))]
     /// Construct a new polygon.
-    pub fn new(points: Vec<Vector>, center: Vector, color: [u8; 3], thickness: i32) -> (Self, Shape) {
+    pub fn new(points: Vec<Vector>, center: Vector, color: Option<Color>, thickness: Option<f64>) -> (Self, Shape) {
+        let color = color.map(|color| color.rgb());
+
         // Bezier curves
         let mut curves = Vec::new();
 
@@ -0,0 +1,42 @@
+//! An open polyline.
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    Bezier,
+    Color,
+    Shape,
+    Vector,
+};
+
+#[pyclass(extends=Shape)]
+#[derive(Clone)]
+/// An open path of straight segments.
+pub struct Polyline;
+
+#[pymethods]
+impl Polyline {
+    #[new]
+    #[pyo3(signature=(points, center=Vector::zero(), color=None, thickness=None))]
+    /// Construct a new polyline through a series of points.
+    /// 
+    /// Unlike a polygon, the last point is not connected back to the first.
+    pub fn pynew(points: Vec<Vector>, center: Vector, color: Option<Color>, thickness: Option<f64>) -> PyResult<(Self, Shape)> {
+        if points.len() < 2 {
+            return Err(PyValueError::new_err("polyline must have at least 2 points"));
+        }
+
+        let color = color.map(|color| color.rgb());
+
+        let curves = points.windows(2)
+            .map(|pair| Bezier::new(pair.to_vec(), Vector::zero(), color, thickness))
+            .collect();
+
+        let shape = Shape::new(curves, center);
+
+        Ok((Self {}, shape))
+    }
+}
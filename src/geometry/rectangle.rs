@@ -4,6 +4,7 @@ use pyo3::prelude::*;
 
 use crate::{
     Bezier,
+    Color,
     Shape,
     Vector,
 };
@@ -17,8 +18,11 @@ pub struct Rectangle;
 #[pymethods]
 impl Rectangle {
     #[new]
+    #[pyo3(signature=(center, width, height, color=None, thickness=None))]
     /// Construct a new rectangle.
-    pub fn pynew(center: Vector, width: f64, height: f64, color: [u8; 3], thickness: i32) -> (Self, Shape) {
+    pub fn pynew(center: Vector, width: f64, height: f64, color: Option<Color>, thickness: Option<f64>) -> (Self, Shape) {
+        let color = color.map(|color| color.rgb());
+
         // Half-sides
         let xside = Vector::new(0.5*width, 0.0);
         let yside = Vector::new(0.0, 0.5*height);
@@ -0,0 +1,55 @@
+//! A regular polygon.
+
+use std::f64::consts::PI;
+
+use image::Rgb;
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    Bezier,
+    Color,
+    Shape,
+    Vector,
+};
+
+#[pyclass(extends=Shape)]
+#[derive(Clone)]
+/// A regular polygon.
+pub struct RegularPolygon;
+
+#[pymethods]
+impl RegularPolygon {
+    #[new]
+    #[pyo3(signature=(center, n, radius, rotation=0.0, color=None, thickness=None))]
+    /// Construct a new regular polygon with `n` sides, whose vertices are `radius` from its center.
+    /// 
+    /// Without rotation, one vertex points straight up.  The rotation is counterclockwise (radians).
+    pub fn pynew(center: Vector, n: usize, radius: f64, rotation: f64, color: Option<Color>, thickness: Option<f64>) -> PyResult<(Self, Shape)> {
+        if n < 3 {
+            return Err(PyValueError::new_err("regular polygon must have at least 3 sides"));
+        }
+
+        let color = color.map(|color| color.rgb());
+
+        // Vertices, counterclockwise from the top
+        let vertices: Vec<Vector> = (0..n).map(|i| {
+            let angle = 0.5 * PI + rotation + 2.0 * PI * i as f64 / n as f64;
+            Vector::new(radius * angle.cos(), radius * angle.sin())
+        }).collect();
+
+        let shape = Shape::new(closed_path(&vertices, color, thickness), center);
+
+        Ok((Self {}, shape))
+    }
+}
+
+/// Construct the sides of a closed path through a series of vertices.
+pub fn closed_path(vertices: &[Vector], color: Option<Rgb<u8>>, thickness: Option<f64>) -> Vec<Bezier> {
+    (0..vertices.len()).map(|i| {
+        Bezier::new(vec![vertices[i], vertices[(i + 1) % vertices.len()]], Vector::zero(), color, thickness)
+    }).collect()
+}
@@ -0,0 +1,79 @@
+//! A rectangle with rounded corners.
+
+use std::f64::consts::PI;
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    arc_curves,
+    Bezier,
+    Color,
+    Shape,
+    Vector,
+};
+
+#[pyclass(extends=Shape)]
+#[pyo3(name = "RoundedRect")]
+#[derive(Clone)]
+/// A rectangle with rounded corners.
+pub struct RoundedRectangle;
+
+#[pymethods]
+impl RoundedRectangle {
+    #[new]
+    #[pyo3(signature=(center, width, height, corner_radius, color=None, thickness=None))]
+    /// Construct a new rounded rectangle.
+    /// 
+    /// The corner radius is limited to half of the shorter side.  A rounded rectangle with
+    /// no width or height is a single point at its center.
+    pub fn pynew(center: Vector, width: f64, height: f64, corner_radius: f64, color: Option<Color>, thickness: Option<f64>) -> PyResult<(Self, Shape)> {
+        if width.is_nan() || height.is_nan() || width < 0.0 || height < 0.0 {
+            return Err(PyValueError::new_err("rounded rectangle must have a non-negative width and height"));
+        }
+
+        if corner_radius.is_nan() {
+            return Err(PyValueError::new_err("rounded rectangle corner radius must be a number"));
+        }
+
+        let color = color.map(|color| color.rgb());
+        let radius = corner_radius.clamp(0.0, 0.5 * width.min(height));
+
+        // Half-sides, up to the start of the corners
+        let x = 0.5 * width - radius;
+        let y = 0.5 * height - radius;
+
+        // Centers of the corners, counterclockwise from the top right
+        let corners = [Vector::new(x, y), Vector::new(-x, y), Vector::new(-x, -y), Vector::new(x, -y)];
+
+        let mut curves = Vec::new();
+        for (i, corner) in corners.iter().enumerate() {
+            let start = 0.5 * PI * i as f64;
+
+            // Corner, then the side to the next corner
+            if radius > 0.0 {
+                curves.extend(arc_curves(*corner, (radius, radius), start, start + 0.5 * PI, color, thickness));
+            }
+
+            let next = corners[(i + 1) % 4];
+            let (a, b) = ((start + 0.5 * PI).cos(), (start + 0.5 * PI).sin());
+            let from = *corner + Vector::new(radius * a, radius * b);
+            let to = next + Vector::new(radius * a, radius * b);
+
+            if (to - from).norm() > 0.0 {
+                curves.push(Bezier::new(vec![from, to], Vector::zero(), color, thickness));
+            }
+        }
+
+        // Nothing is left of a rectangle with no size but its center
+        if curves.is_empty() {
+            curves.push(Bezier::new(vec![Vector::zero(); 2], Vector::zero(), color, thickness));
+        }
+
+        let shape = Shape::new(curves, center);
+
+        Ok((Self {}, shape))
+    }
+}
@@ -0,0 +1,48 @@
+//! A sector of a circle.
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    arc_curves,
+    Bezier,
+    Color,
+    Shape,
+    Vector,
+};
+
+#[pyclass(extends=Shape)]
+#[derive(Clone)]
+/// A sector of a circle (a pie slice), bounded by two radii and an arc.
+pub struct Sector;
+
+#[pymethods]
+impl Sector {
+    #[new]
+    #[pyo3(signature=(center, radius, start, end, color=None, thickness=None))]
+    /// Construct a new sector from angle `start` to angle `end`.
+    /// 
+    /// Angles are in radians, counterclockwise from the X axis.
+    pub fn pynew(center: Vector, radius: f64, start: f64, end: f64, color: Option<Color>, thickness: Option<f64>) -> PyResult<(Self, Shape)> {
+        if !start.is_finite() || !end.is_finite() {
+            return Err(PyValueError::new_err("sector angles must be finite"));
+        }
+
+        let color = color.map(|color| color.rgb());
+
+        // Ends of the arc
+        let first = Vector::new(radius * start.cos(), radius * start.sin());
+        let last = Vector::new(radius * end.cos(), radius * end.sin());
+
+        // Out along one radius, around the arc, and back along the other radius
+        let mut curves = vec![Bezier::new(vec![Vector::zero(), first], Vector::zero(), color, thickness)];
+        curves.extend(arc_curves(Vector::zero(), (radius, radius), start, end, color, thickness));
+        curves.push(Bezier::new(vec![last, Vector::zero()], Vector::zero(), color, thickness));
+
+        let shape = Shape::new(curves, center);
+
+        Ok((Self {}, shape))
+    }
+}
@@ -1,22 +1,73 @@
 //! A shape make from Bezier curves.
 
-use image::{
-    Rgb,
-    RgbImage,
-};
+use std::borrow::Cow;
+
+use image::Rgb;
 
-use pyo3::prelude::*;
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
 
 use crate::{
+    bounds,
+    combine,
+    distinct,
     Animate,
     Animation,
+    Arrowheads,
     Artist,
     Bezier,
+    BooleanOp,
+    ColorSpace,
+    Frame,
     Interpolate,
+    LineCap,
+    LineJoin,
+    March,
+    Paint,
+    Stroke,
     Trace,
     Vector,
 };
 
+/// Curves whose ends are closer than this (world units) are considered connected.
+const CONNECTED: f64 = 1E-6;
+
+/// A connected run of curves in a shape, sampled into points.
+pub struct Path {
+    /// Points along this path.
+    pub points: Vec<Vector>,
+
+    /// Position along the shape (between 0 and 1) of each point.
+    pub params: Vec<f64>,
+
+    /// Does this path end where it begins?
+    pub closed: bool,
+
+    /// Color of this path, or `None` for the theme's foreground color.
+    pub color: Option<Rgb<u8>>,
+
+    /// Stroke style of this path.
+    pub stroke: Stroke,
+
+    /// Arc length along the shape at which this path begins (world units), used to
+    /// keep dashes in place when only part of a shape is drawn.
+    pub distance: f64,
+}
+
+impl Path {
+    /// Stroke this path on a frame.
+    pub fn draw(&self, frame: &mut Frame) {
+        let stroke = Stroke {
+            dash_offset: self.stroke.dash_offset + self.distance,
+            ..self.stroke.clone()
+        };
+
+        frame.stroke(&self.points, &self.params, self.closed, &stroke, self.color);
+    }
+}
+
 #[pyclass(subclass)]
 #[derive(Clone)]
 /// A shape made from Bezier curves.
@@ -24,14 +75,20 @@ pub struct Shape {
     /// Curves contained in this shape.
     pub curves: Vec<Bezier>,
 
-    /// Thickness of this shape.
-    pub thickness: i32,
+    /// Stroke style of this shape.
+    pub stroke: Stroke,
     
     /// Origin of this shape.
     pub origin: Vector,
 
-    /// Color of this shape.
-    pub color: Rgb<u8>,
+    /// Color of this shape, or `None` for the theme's foreground color.
+    pub color: Option<Rgb<u8>>,
+
+    /// Paint filling the inside of this shape, if any.
+    pub fill: Option<Paint>,
+
+    /// Arrowheads sized whenever this shape is drawn, if it is an arrow without a head size.
+    pub arrowheads: Option<Arrowheads>,
 }
 
 #[pymethods]
@@ -41,21 +98,124 @@ impl Shape {
     pub fn new(curves: Vec<Bezier>, origin: Vector) -> Self {
         Self {
             color: curves[0].color,
-            thickness: curves[0].thickness,
+            stroke: curves[0].stroke.clone(),
             origin,
             curves,
+            fill: None,
+            arrowheads: None,
         }
     }
 
+    #[getter]
+    /// Shape of the ends of this shape's outline, if it is open.
+    pub fn get_cap(&self) -> &'static str {
+        self.stroke.cap.name()
+    }
+
+    #[setter]
+    /// Set the shape of the ends of this shape's outline (`"butt"`, `"round"`, or `"square"`).
+    pub fn set_cap(&mut self, cap: LineCap) {
+        self.stroke.cap = cap;
+
+        for curve in self.curves.iter_mut() {
+            curve.stroke.cap = cap;
+        }
+    }
+
+    #[getter]
+    /// Shape of the corners of this shape's outline.
+    pub fn get_join(&self) -> &'static str {
+        self.stroke.join.name()
+    }
+
+    #[setter]
+    /// Set the shape of the corners of this shape's outline (`"miter"`, `"round"`, or `"bevel"`).
+    pub fn set_join(&mut self, join: LineJoin) {
+        self.stroke.join = join;
+
+        for curve in self.curves.iter_mut() {
+            curve.stroke.join = join;
+        }
+    }
+
+    #[getter]
+    /// Dash pattern of this shape's outline, as alternating dash and gap lengths (world units).
+    pub fn get_dash(&self) -> Vec<f64> {
+        self.stroke.dash.clone()
+    }
+
+    #[setter]
+    /// Set the dash pattern of this shape's outline (an empty list gives a solid outline).
+    pub fn set_dash(&mut self, dash: Vec<f64>) -> PyResult<()> {
+        self.stroke.set_dash(dash)?;
+
+        for curve in self.curves.iter_mut() {
+            curve.stroke.dash = self.stroke.dash.clone();
+        }
+
+        Ok(())
+    }
+
+    #[getter]
+    /// Distance (world units) into the dash pattern at which this shape's outline begins.
+    pub fn get_dash_offset(&self) -> f64 {
+        self.stroke.dash_offset
+    }
+
+    #[setter]
+    /// Set the distance (world units) into the dash pattern at which this shape's outline begins.
+    pub fn set_dash_offset(&mut self, offset: f64) {
+        self.stroke.dash_offset = offset;
+
+        for curve in self.curves.iter_mut() {
+            curve.stroke.dash_offset = offset;
+        }
+    }
+
+    #[getter]
+    /// Paint of this shape's outline, if it is not a solid color.
+    pub fn get_paint(&self) -> Option<Paint> {
+        self.stroke.paint.clone()
+    }
+
+    #[setter]
+    /// Set the paint of this shape's outline (`None` for its solid color).
+    pub fn set_paint(&mut self, paint: Option<Paint>) {
+        self.stroke.paint = paint;
+
+        for curve in self.curves.iter_mut() {
+            curve.stroke.paint = self.stroke.paint.clone();
+        }
+    }
+
+    #[getter]
+    /// Paint filling the inside of this shape, if any.
+    pub fn get_fill(&self) -> Option<Paint> {
+        self.fill.clone()
+    }
+
+    #[setter]
+    /// Set the paint filling the inside of this shape (`None` for no fill).
+    pub fn set_fill(&mut self, fill: Option<Paint>) -> PyResult<()> {
+        if fill.as_ref().is_some_and(Paint::is_along) {
+            return Err(PyValueError::new_err("fills cannot be painted along a stroke"));
+        }
+
+        self.fill = fill;
+
+        Ok(())
+    }
+
     #[getter]
     /// Construct a (static) animation from this shape.
     pub fn get_display(&self) -> Animation {
         Animate::animate(self)
     }
 
-    /// Interpolate this shape with another.
-    pub fn into(&self, other: Shape) -> Animation {
-        Interpolate::new(self.clone(), other).animate()
+    #[pyo3(signature=(other, space=ColorSpace::Oklab))]
+    /// Interpolate this shape with another, blending colors in a given color space.
+    pub fn into(&self, other: Shape, space: ColorSpace) -> Animation {
+        Interpolate::new(self.clone(), other, space).animate()
     }
 
     #[getter]
@@ -69,13 +229,139 @@ impl Shape {
     pub fn get_untrace(&self) -> Animation {
         Trace::new(self.clone(), true).animate()
     }
+
+    /// Construct an animation that moves the dashes of this shape forward by some distance (world units).
+    pub fn march(&self, distance: f64) -> Animation {
+        March::new(self.clone(), distance).animate()
+    }
+
+    /// Find the smallest box `(min, max)` containing this shape.
+    pub fn bounding_box(&self) -> (Vector, Vector) {
+        if self.curves.is_empty() {
+            return (self.origin, self.origin);
+        }
+
+        let boxes: Vec<(Vector, Vector)> = self.placed().iter().map(|curve| curve.bounding_box()).collect();
+
+        bounds(boxes.into_iter().flat_map(|(min, max)| [min, max]))
+    }
+
+    /// Find the center of this shape's bounding box.
+    pub fn center(&self) -> Vector {
+        let (min, max) = self.bounding_box();
+
+        (min + max) * 0.5
+    }
+
+    /// Find the width of this shape's bounding box.
+    pub fn width(&self) -> f64 {
+        let (min, max) = self.bounding_box();
+
+        max.x - min.x
+    }
+
+    /// Find the height of this shape's bounding box.
+    pub fn height(&self) -> f64 {
+        let (min, max) = self.bounding_box();
+
+        max.y - min.y
+    }
+
+    /// Find the points where the outline of this shape crosses the outline of another.
+    pub fn intersections(&self, other: Shape) -> Vec<Vector> {
+        let others = other.placed();
+        let points = self.placed().iter()
+            .flat_map(|curve| others.iter().flat_map(|other| curve.intersections(other.clone())))
+            .collect();
+
+        distinct(points)
+    }
+
+    /// Find the points where the outline of this shape crosses the (infinite) line through
+    /// `start` and `end`.
+    pub fn line_intersections(&self, start: Vector, end: Vector) -> Vec<Vector> {
+        let points = self.placed().iter().flat_map(|curve| curve.line_intersections(start, end)).collect();
+
+        distinct(points)
+    }
+
+    /// Find the point on the outline of this shape nearest to a given point.
+    pub fn nearest_point(&self, point: Vector) -> Vector {
+        self.placed().iter()
+            .map(|curve| curve.nearest_point(point))
+            .min_by(|a, b| (*a - point).norm().total_cmp(&(*b - point).norm()))
+            .unwrap_or(self.origin)
+    }
+
+    /// Is a point inside this shape?
+    /// 
+    /// The inside is found with the nonzero winding rule, as when the shape is filled.
+    pub fn contains(&self, point: Vector) -> bool {
+        let mut winding = 0;
+
+        for contour in self.contours(Vector::zero()) {
+            for (a, b) in contour.iter().zip(contour.iter().cycle().skip(1)) {
+                let side = (*b - *a).cross(point - *a);
+
+                if a.y <= point.y && b.y > point.y && side > 0.0 {
+                    winding += 1;
+                } else if a.y > point.y && b.y <= point.y && side < 0.0 {
+                    winding -= 1;
+                }
+            }
+        }
+
+        winding != 0
+    }
+
+    /// Construct the shape covering the inside of this shape or another (or both).
+    pub fn union(&self, other: Shape) -> PyResult<Shape> {
+        combine(self, &other, BooleanOp::Union)
+    }
+
+    /// Construct the shape covering the inside of both this shape and another.
+    pub fn intersection(&self, other: Shape) -> PyResult<Shape> {
+        combine(self, &other, BooleanOp::Intersection)
+    }
+
+    /// Construct the shape covering the inside of this shape, but not another.
+    pub fn difference(&self, other: Shape) -> PyResult<Shape> {
+        combine(self, &other, BooleanOp::Difference)
+    }
+
+    /// Construct the shape covering the inside of exactly one of this shape and another.
+    pub fn xor(&self, other: Shape) -> PyResult<Shape> {
+        combine(self, &other, BooleanOp::Xor)
+    }
 }
 
 impl Shape {
+    /// Curves of this shape, moved by its origin.
+    pub fn placed(&self) -> Vec<Bezier> {
+        self.curves.iter().map(|curve| curve.moved(self.origin)).collect()
+    }
+
+    /// Curves of this shape (moved by its origin), grouped into runs of consecutive curves
+    /// that connect.
+    pub fn outlines(&self) -> Vec<Vec<Bezier>> {
+        let mut outlines: Vec<Vec<Bezier>> = Vec::new();
+
+        for curve in self.placed() {
+            match outlines.last_mut() {
+                Some (outline) if (outline[outline.len() - 1].trace(1.0) - curve.trace(0.0)).norm() < CONNECTED => {
+                    outline.push(curve);
+                },
+                _ => outlines.push(vec![curve]),
+            }
+        }
+
+        outlines
+    }
+
     /// Trace this shape of Bezier curves.
     pub fn trace(&self, t: f64) -> Vector {
         // No curve if no points :(
-        if self.curves.len() == 0 {
+        if self.curves.is_empty() {
             return Vector::zero();
         }
 
@@ -93,12 +379,130 @@ impl Shape {
 
         self.origin + self.curves[idx].trace(t_curve)
     }
+
+    /// Does the outline of this shape end where it begins?
+    pub fn is_closed(&self) -> bool {
+        match (self.curves.first(), self.curves.last()) {
+            (Some (first), Some (last)) => (first.trace(0.0) - last.trace(1.0)).norm() < CONNECTED,
+            _ => false,
+        }
+    }
+
+    /// This shape as it is drawn on a frame, with the heads of an arrow without a head size
+    /// sized to its thickness.
+    pub fn sized(&self, frame: &Frame) -> Cow<'_, Shape> {
+        match &self.arrowheads {
+            Some (arrowheads) => Cow::Owned(arrowheads.shape(self, frame)),
+            None => Cow::Borrowed(self),
+        }
+    }
+
+    /// Sample the outline of this shape into closed contours, placed at a given location.
+    /// 
+    /// Consecutive curves that connect belong to the same contour.
+    pub fn contours(&self, location: Vector) -> Vec<Vec<Vector>> {
+        let mut contours: Vec<Vec<Vector>> = Vec::new();
+
+        for curve in &self.curves {
+            let points: Vec<Vector> = curve.sample(0.0, 1.0).into_iter()
+                .map(|point| location + self.origin + point)
+                .collect();
+
+            match contours.last_mut() {
+                Some (contour) if (contour[contour.len() - 1] - points[0]).norm() < CONNECTED => {
+                    contour.extend_from_slice(&points[1..]);
+                },
+                _ => contours.push(points),
+            }
+        }
+
+        contours
+    }
+
+    /// Sample this shape into paths of points, placed at a given location.
+    /// 
+    /// The `range` function gives the range of `t` to sample on each curve, or `None`
+    /// to skip that curve.  Consecutive curves that connect and share the same color
+    /// and stroke style are joined into a single path.
+    pub fn paths(&self, location: Vector, range: impl Fn(usize) -> Option<(f64, f64)>) -> Vec<Path> {
+        let mut paths: Vec<Path> = Vec::new();
+
+        // Is every curve sampled completely?
+        let mut complete = true;
+
+        // Was the previous curve skipped?
+        let mut skipped = false;
+
+        // Arc length of the shape before the current curve (only needed for dashes)
+        let mut distance = 0.0;
+
+        for (i, curve) in self.curves.iter().enumerate() {
+            let dashed = !curve.stroke.dash.is_empty();
+            let length = if dashed { curve.arc_length(0.0, 1.0) } else { 0.0 };
+
+            let (start, end) = match range(i) {
+                Some (range) => range,
+                None => {
+                    complete = false;
+                    skipped = true;
+                    distance += length;
+                    continue;
+                },
+            };
+            complete &= start == 0.0 && end == 1.0;
+
+            // Points along this curve, and their positions along the shape
+            let points: Vec<Vector> = curve.sample(start, end).into_iter()
+                .map(|point| location + self.origin + point)
+                .collect();
+            let steps = (points.len() - 1) as f64;
+            let params: Vec<f64> = (0..points.len())
+                .map(|k| (i as f64 + start + (end - start) * k as f64 / steps) / self.curves.len() as f64)
+                .collect();
+
+            // Continue the previous path, if possible
+            match paths.last_mut() {
+                Some (path) if !skipped
+                    && path.color == curve.color
+                    && path.stroke == curve.stroke
+                    && (path.points[path.points.len() - 1] - points[0]).norm() < CONNECTED => {
+                    path.points.extend_from_slice(&points[1..]);
+                    path.params.extend_from_slice(&params[1..]);
+                },
+                _ => paths.push(Path {
+                    points,
+                    params,
+                    closed: false,
+                    color: curve.color,
+                    stroke: curve.stroke.clone(),
+                    distance: if dashed { distance + curve.arc_length(0.0, start) } else { 0.0 },
+                }),
+            }
+
+            skipped = false;
+            distance += length;
+        }
+
+        // Close the outline of a complete shape, if it ends where it begins
+        if complete && paths.len() == 1 {
+            let path = &mut paths[0];
+            path.closed = (path.points[0] - path.points[path.points.len() - 1]).norm() < CONNECTED;
+        }
+
+        paths
+    }
 }
 
 impl Artist for Shape {
-    fn draw(&self, location: Vector, image: &mut RgbImage) {
-        for curve in &self.curves {
-            curve.draw(self.origin + location, image);
+    fn draw(&self, location: Vector, frame: &mut Frame) {
+        let shape = self.sized(frame);
+
+        if let Some (fill) = &shape.fill {
+            frame.fill(&shape.contours(location), fill, 1.0);
+        }
+
+        for path in shape.paths(location, |_| Some ((0.0, 1.0))) {
+            path.draw(frame);
         }
     }
 }
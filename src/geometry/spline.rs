@@ -0,0 +1,116 @@
+//! A smooth spline through points.
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    Bezier,
+    Color,
+    Shape,
+    Vector,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Kind of a spline, which determines how it bends between its points.
+/// 
+/// From Python, this is given as `"catmull-rom"` or `"natural"`.
+pub enum SplineKind {
+    /// Each tangent is parallel to the line between the neighboring points, so each point
+    /// only affects the nearby curve.
+    CatmullRom,
+
+    /// The curvature is continuous everywhere and zero at the ends, giving the smoothest curve.
+    Natural,
+}
+
+impl<'py> FromPyObject<'py> for SplineKind {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match ob.extract::<String>()?.as_str() {
+            "catmull-rom" => Ok(Self::CatmullRom),
+            "natural" => Ok(Self::Natural),
+            name => Err(PyValueError::new_err(format!("unknown spline kind '{}'", name))),
+        }
+    }
+}
+
+#[pyclass(extends=Shape)]
+#[derive(Clone)]
+/// A smooth curve through a series of points, made of cubic Bezier curves.
+pub struct Spline;
+
+#[pymethods]
+impl Spline {
+    #[new]
+    #[pyo3(signature=(points, kind=SplineKind::CatmullRom, center=Vector::zero(), color=None, thickness=None))]
+    /// Construct a new spline passing through every one of a series of points.
+    pub fn pynew(points: Vec<Vector>, kind: SplineKind, center: Vector, color: Option<Color>, thickness: Option<f64>) -> PyResult<(Self, Shape)> {
+        if points.len() < 2 {
+            return Err(PyValueError::new_err("spline must have at least 2 points"));
+        }
+
+        let color = color.map(|color| color.rgb());
+
+        // Tangent at each point
+        let tangents = match kind {
+            SplineKind::CatmullRom => catmull_rom(&points),
+            SplineKind::Natural => natural(&points),
+        };
+
+        // One cubic curve between each pair of points
+        let curves = (0..points.len() - 1).map(|i| {
+            let (a, b) = (points[i], points[i + 1]);
+            Bezier::new(vec![a, a + tangents[i] * (1.0 / 3.0), b - tangents[i + 1] * (1.0 / 3.0), b], Vector::zero(), color, thickness)
+        }).collect();
+
+        let shape = Shape::new(curves, center);
+
+        Ok((Self {}, shape))
+    }
+}
+
+/// Find the tangents of a Catmull-Rom spline, extending it past each end by reflection.
+fn catmull_rom(points: &[Vector]) -> Vec<Vector> {
+    let n = points.len();
+
+    (0..n).map(|i| {
+        let before = if i > 0 { points[i - 1] } else { points[0] * 2.0 - points[1] };
+        let after = if i + 1 < n { points[i + 1] } else { points[n - 1] * 2.0 - points[n - 2] };
+
+        (after - before) * 0.5
+    }).collect()
+}
+
+/// Find the tangents of a natural cubic spline, by solving its tridiagonal system.
+fn natural(points: &[Vector]) -> Vec<Vector> {
+    let n = points.len();
+
+    // Diagonals and right-hand side of the system
+    let mut diagonal = vec![4.0; n];
+    diagonal[0] = 2.0;
+    diagonal[n - 1] = 2.0;
+
+    let mut rhs: Vec<Vector> = (0..n).map(|i| {
+        let before = points[i.saturating_sub(1)];
+        let after = points[(i + 1).min(n - 1)];
+
+        (after - before) * 3.0
+    }).collect();
+
+    // Forward elimination (all off-diagonal entries are 1)
+    for i in 1..n {
+        let factor = 1.0 / diagonal[i - 1];
+        diagonal[i] -= factor;
+        rhs[i] = rhs[i] - rhs[i - 1] * factor;
+    }
+
+    // Back substitution
+    let mut tangents = vec![Vector::zero(); n];
+    tangents[n - 1] = rhs[n - 1] * (1.0 / diagonal[n - 1]);
+    for i in (0..n - 1).rev() {
+        tangents[i] = (rhs[i] - tangents[i + 1]) * (1.0 / diagonal[i]);
+    }
+
+    tangents
+}
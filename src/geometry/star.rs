@@ -0,0 +1,48 @@
+//! A star.
+
+use std::f64::consts::PI;
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::{
+    closed_path,
+    Color,
+    Shape,
+    Vector,
+};
+
+#[pyclass(extends=Shape)]
+#[derive(Clone)]
+/// A star.
+pub struct Star;
+
+#[pymethods]
+impl Star {
+    #[new]
+    #[pyo3(signature=(center, n, inner, outer, rotation=0.0, color=None, thickness=None))]
+    /// Construct a new star with `n` points, whose points are `outer` from its center and
+    /// whose inner corners are `inner` from its center.
+    /// 
+    /// Without rotation, one point points straight up.  The rotation is counterclockwise (radians).
+    pub fn pynew(center: Vector, n: usize, inner: f64, outer: f64, rotation: f64, color: Option<Color>, thickness: Option<f64>) -> PyResult<(Self, Shape)> {
+        if n < 2 {
+            return Err(PyValueError::new_err("star must have at least 2 points"));
+        }
+
+        let color = color.map(|color| color.rgb());
+
+        // Vertices, alternating between points and inner corners, counterclockwise from the top
+        let vertices: Vec<Vector> = (0..2 * n).map(|i| {
+            let radius = if i % 2 == 0 { outer } else { inner };
+            let angle = 0.5 * PI + rotation + PI * i as f64 / n as f64;
+            Vector::new(radius * angle.cos(), radius * angle.sin())
+        }).collect();
+
+        let shape = Shape::new(closed_path(&vertices, color, thickness), center);
+
+        Ok((Self {}, shape))
+    }
+}
@@ -1,22 +1,38 @@
 //! A 2-dimensional point on an image.
 
+use std::hash::{
+    DefaultHasher,
+    Hash,
+    Hasher,
+};
 use std::ops::{
     Add,
+    Div,
     Sub,
     Mul,
     Neg,
 };
 
-use pyo3::prelude::*;
+use pyo3::{
+    exceptions::PyZeroDivisionError,
+    prelude::*,
+    types::PyIterator,
+};
 
 #[pyclass]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 /// A 2-dimensional vector on an image.
 /// 
 /// The origin of an image is in the center, and
-/// the units of this vector are pixels.
+/// the units of this vector are world units (pixels,
+/// unless the video specifies otherwise).
 pub struct Vector {
+    #[pyo3(get)]
+    /// Horizontal component (rightwards).
     pub x: f64,
+
+    #[pyo3(get)]
+    /// Vertical component (upwards).
     pub y: f64,
 }
 
@@ -39,16 +55,122 @@ impl Vector {
             y: 0.0,
         }
     }
-}
 
-impl Vector {
-    /// Convert this vector into pixel values.
-    pub fn to_pixels(&self, width: u32, height: u32) -> (u32, u32) {
-        let x = (self.x + (width as f64)/2.0).round() as u32;
-        let y = ((height as f64)/2.0 - self.y).round() as u32;
-        
-        // TODO don't even draw the pixel if it's off-screen
-        (x.clamp(0, width-1), y.clamp(0, height-1))
+    #[staticmethod]
+    /// Construct a vector from its length and its counterclockwise angle from the X axis (radians).
+    pub fn polar(radius: f64, angle: f64) -> Self {
+        Self::unit(angle) * radius
+    }
+
+    #[staticmethod]
+    /// Construct a vector of length one at a counterclockwise angle from the X axis (radians).
+    pub fn unit(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+
+        Self::new(cos, sin)
+    }
+
+    #[staticmethod]
+    /// Construct a vector from a tuple `(x, y)`.
+    pub fn from_tuple(tuple: (f64, f64)) -> Self {
+        Self::new(tuple.0, tuple.1)
+    }
+
+    #[pyo3(name = "to_tuple")]
+    /// Convert this vector into a tuple `(x, y)`.
+    pub fn py_to_tuple(&self) -> (f64, f64) {
+        (self.x, self.y)
+    }
+
+    /// Compute the dot product of this vector with another.
+    pub fn dot(&self, other: Vector) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Compute the (scalar) cross product of this vector with another.
+    pub fn cross(&self, other: Vector) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Compute the length of this vector.
+    pub fn norm(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// Construct a vector of length one in the same direction as this vector (or the zero
+    /// vector, if this vector has no direction).
+    pub fn normalize(&self) -> Vector {
+        let norm = self.norm();
+
+        if norm > 0.0 { *self / norm } else { Vector::zero() }
+    }
+
+    #[pyo3(signature=(angle, center=Vector::zero()))]
+    /// Rotate this vector counterclockwise by an angle (radians) about a center.
+    pub fn rotate(&self, angle: f64, center: Vector) -> Vector {
+        let (sin, cos) = angle.sin_cos();
+        let offset = *self - center;
+
+        center + Vector::new(offset.x * cos - offset.y * sin, offset.x * sin + offset.y * cos)
+    }
+
+    /// Compute the counterclockwise angle of this vector from the X axis (radians, between -pi and pi).
+    pub fn angle(&self) -> f64 {
+        self.y.atan2(self.x)
+    }
+
+    /// Interpolate linearly between this vector (at `t = 0`) and another (at `t = 1`).
+    pub fn lerp(&self, other: Vector, t: f64) -> Vector {
+        *self * (1.0 - t) + other * t
+    }
+
+    fn __add__(&self, other: Vector) -> Vector {
+        *self + other
+    }
+
+    fn __sub__(&self, other: Vector) -> Vector {
+        *self - other
+    }
+
+    fn __mul__(&self, other: f64) -> Vector {
+        *self * other
+    }
+
+    fn __rmul__(&self, other: f64) -> Vector {
+        *self * other
+    }
+
+    fn __truediv__(&self, other: f64) -> PyResult<Vector> {
+        if other == 0.0 {
+            return Err(PyZeroDivisionError::new_err("cannot divide a vector by zero"));
+        }
+
+        Ok(*self / other)
+    }
+
+    fn __neg__(&self) -> Vector {
+        -*self
+    }
+
+    fn __eq__(&self, other: Vector) -> bool {
+        *self == other
+    }
+
+    fn __hash__(&self) -> u64 {
+        // Adding zero turns -0.0 into 0.0, so that equal vectors hash equally
+        let mut hasher = DefaultHasher::new();
+        (self.x + 0.0).to_bits().hash(&mut hasher);
+        (self.y + 0.0).to_bits().hash(&mut hasher);
+
+        hasher.finish()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        (self.x, self.y).into_pyobject(py)?.try_iter()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Vector({:?}, {:?})", self.x, self.y)
     }
 }
 
@@ -96,6 +218,17 @@ impl Mul<u32> for Vector {
     }
 }
 
+impl Div<f64> for Vector {
+    type Output = Vector;
+
+    fn div(self, other: f64) -> Self::Output {
+        Self {
+            x: self.x / other,
+            y: self.y / other,
+        }
+    }
+}
+
 impl Neg for Vector {
     type Output = Vector;
 
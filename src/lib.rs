@@ -3,73 +3,142 @@
 #![deny(warnings)]
 #![deny(missing_docs)]
 
+mod camera;
 mod drawing;
 mod geometry;
+mod theme;
 mod video;
 
 use pyo3::prelude::*;
 
 use image::{
-    Rgb,
-    RgbImage,
+    Rgba,
+    Rgba32FImage,
 };
 
+use camera::Camera;
 use drawing::{
+    byte_to_linear,
+    linear_to_byte,
+    rasterize,
+    signed_area,
+    AlongStroke,
     Animate,
     Animation,
     Artist,
-    Bresenham,
-    Brush,
+    BlendMode,
+    Clip,
+    ClipRegion,
+    Color,
+    ColorSpace,
+    Compositing,
+    Effect,
+    Frame,
     Interpolate,
+    LineCap,
+    LineJoin,
+    March,
+    Mask,
+    Paint,
+    Stroke,
+    Style,
     Trace,
     TracedShape,
+    WipeDirection,
 };
 use geometry::{
+    arc_curves,
+    bounds,
+    closed_path,
+    combine,
+    distinct,
+    Annulus,
+    Arrow,
+    ArrowHead,
+    Arrowheads,
     Bezier,
+    BooleanOp,
     Circle,
+    CircularArc,
+    Ellipse,
+    Image,
+    Line,
     LinearAxes,
     Parametric,
     Polygon,
+    Polyline,
     Rectangle,
+    RegularPolygon,
+    RoundedRectangle,
+    Sector,
     Shape,
+    Spline,
+    Star,
     Vector,
 };
+use theme::Theme;
 use video::Video;
 
 /// Interpolation step size.
 pub const STEP: f64 = 1E-3;
 
-/// Add a pixel to the image with a given strength.
-pub fn add_pixel(image: &mut RgbImage, x: u32, y: u32, color: Rgb<u8>, strength: f64) {
+/// Find the pixel region `(x0, y0, x1, y1)` of an image that is not transparent, if any.
+pub fn drawn(image: &Rgba32FImage) -> Option<(u32, u32, u32, u32)> {
+    let mut region: Option<(u32, u32, u32, u32)> = None;
+
+    for (x, y, pixel) in image.enumerate_pixels() {
+        if pixel[3] > 0.0 {
+            region = Some (match region {
+                Some ((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x + 1), y1.max(y + 1)),
+                None => (x, y, x + 1, y + 1),
+            });
+        }
+    }
+
+    region
+}
+
+/// Add a pixel (in the image's own encoding) to the image with a given strength and blend mode.
+pub fn add_pixel(image: &mut Rgba32FImage, x: u32, y: u32, color: Rgba<f32>, strength: f64, compositing: Compositing, mode: BlendMode) {
     // Don't draw outside the image
     if x >= image.width() || y >= image.height() {
         return;
     }
 
     // Current pixel
-    let current_pixel = image.get_pixel(x, y);
-
-    // Interpolated pixel
-    let new_pixel = Rgb ([
-        ((color[0] as f64) * strength) as u8 + ((current_pixel[0] as f64) * (1.0 - strength)) as u8,
-        ((color[1] as f64) * strength) as u8 + ((current_pixel[1] as f64) * (1.0 - strength)) as u8,
-        ((color[2] as f64) * strength) as u8 + ((current_pixel[2] as f64) * (1.0 - strength)) as u8,
-    ]);
+    let current_pixel = *image.get_pixel(x, y);
 
-    image.put_pixel(x, y, new_pixel);
+    image.put_pixel(x, y, compositing.blend(current_pixel, color, strength, mode));
 }
 
 /// Python interface for Beaver Studio.
 #[pymodule]
 fn beaverstudio(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Add classes
+    m.add_class::<Annulus>()?;
+    m.add_class::<Arrow>()?;
     m.add_class::<Bezier>()?;
+    m.add_class::<Camera>()?;
     m.add_class::<Circle>()?;
+    m.add_class::<CircularArc>()?;
+    m.add_class::<Color>()?;
+    m.add_class::<Effect>()?;
+    m.add_class::<Ellipse>()?;
+    m.add_class::<Image>()?;
+    m.add_class::<Line>()?;
     m.add_class::<LinearAxes>()?;
+    m.add_class::<Paint>()?;
     m.add_class::<Parametric>()?;
     m.add_class::<Polygon>()?;
+    m.add_class::<Polyline>()?;
     m.add_class::<Rectangle>()?;
+    m.add_class::<RegularPolygon>()?;
+    m.add_class::<RoundedRectangle>()?;
+    m.add_class::<Sector>()?;
     m.add_class::<Shape>()?;
+    m.add_class::<Spline>()?;
+    m.add_class::<Star>()?;
+    m.add_class::<Theme>()?;
     m.add_class::<Vector>()?;
     m.add_class::<Video>()?;
     
@@ -0,0 +1,186 @@
+//! A theme.
+
+use image::Rgb;
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
+
+use crate::Color;
+
+#[pyclass]
+#[derive(Clone, Debug)]
+/// A theme, which gives the colors and thickness of objects that don't specify their own.
+/// 
+/// Objects without a color are drawn in the foreground color, objects without a
+/// thickness are drawn with the default thickness, and axes without colors are drawn
+/// in the axis colors.  The accent palette holds further colors to pick from.
+pub struct Theme {
+    /// Background color.
+    pub background: Rgb<u8>,
+
+    /// Color of objects that don't specify one.
+    pub foreground: Rgb<u8>,
+
+    /// Palette of accent colors.
+    pub accents: Vec<Rgb<u8>>,
+
+    /// Color of major axis lines.
+    pub major_axis: Rgb<u8>,
+
+    /// Color of minor axis lines.
+    pub minor_axis: Rgb<u8>,
+
+    #[pyo3(get, set)]
+    /// Thickness of objects that don't specify one (pixels of the output video, whether or
+    /// not the video uses world units).
+    pub thickness: f64,
+}
+
+#[pymethods]
+impl Theme {
+    #[new]
+    #[pyo3(signature=(background=None, foreground=None, accents=None, major_axis=None, minor_axis=None, thickness=None))]
+    /// Construct a new theme, starting from the dark theme.
+    pub fn new(
+        background: Option<Color>,
+        foreground: Option<Color>,
+        accents: Option<Vec<Color>>,
+        major_axis: Option<Color>,
+        minor_axis: Option<Color>,
+        thickness: Option<f64>,
+    ) -> PyResult<Self> {
+        let dark = Self::dark();
+
+        let accents = match accents {
+            Some (accents) if accents.is_empty() => return Err(PyValueError::new_err("theme must have at least one accent color")),
+            Some (accents) => accents.iter().map(Color::rgb).collect(),
+            None => dark.accents,
+        };
+
+        Ok(Self {
+            background: background.map_or(dark.background, |color| color.rgb()),
+            foreground: foreground.map_or(dark.foreground, |color| color.rgb()),
+            accents,
+            major_axis: major_axis.map_or(dark.major_axis, |color| color.rgb()),
+            minor_axis: minor_axis.map_or(dark.minor_axis, |color| color.rgb()),
+            thickness: thickness.unwrap_or(dark.thickness),
+        })
+    }
+
+    #[staticmethod]
+    /// Construct the dark theme (light objects on a black background).
+    pub fn dark() -> Self {
+        Self {
+            background: Rgb ([0, 0, 0]),
+            foreground: Rgb ([255, 255, 255]),
+            accents: vec![
+                Rgb ([88, 196, 221]),
+                Rgb ([252, 98, 85]),
+                Rgb ([131, 193, 103]),
+                Rgb ([240, 172, 95]),
+                Rgb ([154, 114, 172]),
+            ],
+            major_axis: Rgb ([255, 255, 255]),
+            minor_axis: Rgb ([160, 160, 160]),
+            thickness: 2.0,
+        }
+    }
+
+    #[staticmethod]
+    /// Construct the light theme (dark objects on a white background).
+    pub fn light() -> Self {
+        Self {
+            background: Rgb ([255, 255, 255]),
+            foreground: Rgb ([0, 0, 0]),
+            accents: vec![
+                Rgb ([31, 119, 180]),
+                Rgb ([214, 39, 40]),
+                Rgb ([44, 160, 44]),
+                Rgb ([255, 127, 14]),
+                Rgb ([148, 103, 189]),
+            ],
+            major_axis: Rgb ([0, 0, 0]),
+            minor_axis: Rgb ([180, 180, 180]),
+            thickness: 2.0,
+        }
+    }
+
+    /// Accent color at an index, wrapping around the palette.
+    pub fn accent(&self, index: usize) -> Color {
+        self.accents[index % self.accents.len()].into()
+    }
+
+    #[getter]
+    /// Background color.
+    pub fn get_background(&self) -> Color {
+        self.background.into()
+    }
+
+    #[setter]
+    /// Set the background color.
+    pub fn set_background(&mut self, color: Color) {
+        self.background = color.rgb();
+    }
+
+    #[getter]
+    /// Color of objects that don't specify one.
+    pub fn get_foreground(&self) -> Color {
+        self.foreground.into()
+    }
+
+    #[setter]
+    /// Set the color of objects that don't specify one.
+    pub fn set_foreground(&mut self, color: Color) {
+        self.foreground = color.rgb();
+    }
+
+    #[getter]
+    /// Palette of accent colors.
+    pub fn get_accents(&self) -> Vec<Color> {
+        self.accents.iter().map(|color| (*color).into()).collect()
+    }
+
+    #[setter]
+    /// Set the palette of accent colors.
+    pub fn set_accents(&mut self, accents: Vec<Color>) -> PyResult<()> {
+        if accents.is_empty() {
+            return Err(PyValueError::new_err("theme must have at least one accent color"));
+        }
+
+        self.accents = accents.iter().map(Color::rgb).collect();
+
+        Ok(())
+    }
+
+    #[getter]
+    /// Color of major axis lines.
+    pub fn get_major_axis(&self) -> Color {
+        self.major_axis.into()
+    }
+
+    #[setter]
+    /// Set the color of major axis lines.
+    pub fn set_major_axis(&mut self, color: Color) {
+        self.major_axis = color.rgb();
+    }
+
+    #[getter]
+    /// Color of minor axis lines.
+    pub fn get_minor_axis(&self) -> Color {
+        self.minor_axis.into()
+    }
+
+    #[setter]
+    /// Set the color of minor axis lines.
+    pub fn set_minor_axis(&mut self, color: Color) {
+        self.minor_axis = color.rgb();
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
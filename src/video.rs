@@ -5,17 +5,17 @@ use std::{
     fs,
 };
 
-use image::{
-    Rgb,
-    RgbImage,
-};
+use image::Rgb;
 
 use indicatif::{
     ProgressBar,
     ProgressStyle,
 };
 
-use pyo3::prelude::*;
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+};
 
 use rayon::{
     prelude::*,
@@ -23,18 +23,184 @@ use rayon::{
 };
 
 use crate::{
+    add_pixel,
     Animation,
+    BlendMode,
+    Camera,
+    Color,
+    Compositing,
+    Effect,
+    Frame,
+    Image,
     LinearAxes,
     Parametric,
     Shape,
+    STEP,
+    Theme,
     Vector,
 };
 
 /// Time required to trace an object (seconds).
 pub const TRACE_TIME: f64 = 1.0;
 
-/// Type alias for an animation with its location and start/stop frames.
-type Instance = (Animation, Vector, u32, u32);
+/// Names of the default layers, from bottom to top.
+pub const DEFAULT_LAYERS: [&str; 3] = ["background", "content", "overlay"];
+
+/// Layer that animations are added to when none is specified.
+pub const DEFAULT_LAYER: &str = "content";
+
+#[derive(Clone)]
+/// An animation scheduled in a video.
+struct Instance {
+    /// Animation to be played.
+    animation: Animation,
+
+    /// Location of this animation.
+    location: Vector,
+
+    /// Start frame.
+    start: u32,
+
+    /// End frame.
+    end: u32,
+
+    /// Index of the layer this animation is drawn on.
+    layer: usize,
+
+    /// Depth of this animation within its layer (higher is drawn later).
+    z: f64,
+
+    /// How this animation is blended with what is beneath it.
+    blend: BlendMode,
+
+    /// Effects applied to this animation.
+    effects: Vec<Effect>,
+}
+
+#[derive(Clone)]
+/// A named layer of animations.
+/// 
+/// Layers are drawn in order, so every animation on a later layer sits on
+/// top of every animation on an earlier layer.
+struct Layer {
+    /// Layer name.
+    name: String,
+
+    /// Layer opacity (between 0 and 1).
+    opacity: f64,
+
+    /// Is this layer visible?
+    visible: bool,
+
+    /// How this layer is blended with the layers beneath it.
+    blend: BlendMode,
+
+    /// Effects applied to this layer as a whole.
+    effects: Vec<Effect>,
+}
+
+impl Layer {
+    /// Construct a new layer.
+    fn new(name: &str, opacity: f64, visible: bool, blend: BlendMode, effects: Vec<Effect>) -> Self {
+        Self {
+            name: name.to_string(),
+            opacity: opacity.clamp(0.0, 1.0),
+            visible,
+            blend,
+            effects,
+        }
+    }
+}
+
+#[derive(Clone)]
+/// A movement of the camera.
+enum CameraMotion {
+    /// Move smoothly towards a new camera position, zoom, and rotation.
+    To (Camera),
+
+    /// Follow the outline of a shape (placed at a location), as it would be traced.
+    Follow (Box<Shape>, Vector),
+}
+
+#[derive(Clone)]
+/// A movement of the camera, scheduled in a video.
+struct CameraMove {
+    /// Camera motion.
+    motion: CameraMotion,
+
+    /// Start frame.
+    start: u32,
+
+    /// End frame.
+    end: u32,
+}
+
+#[derive(Clone, Copy)]
+/// Render quality of a video.
+/// 
+/// From Python, this is given either as the name of a preset (`"draft"`, `"normal"`,
+/// `"high"`, or `"ultra"`) or as an integer supersampling factor.
+pub struct Quality {
+    /// Each side of the frame is rendered at this multiple of the output resolution,
+    /// then downsampled.
+    supersample: u32,
+
+    /// Are lines anti-aliased?
+    antialias: bool,
+}
+
+impl Quality {
+    /// Draft quality, with no anti-aliasing, for fast previews.
+    pub const DRAFT: Self = Self { supersample: 1, antialias: false };
+
+    /// Normal quality, with anti-aliased lines.
+    pub const NORMAL: Self = Self { supersample: 1, antialias: true };
+
+    /// High quality, with anti-aliased lines and 2x supersampling.
+    pub const HIGH: Self = Self { supersample: 2, antialias: true };
+
+    /// Ultra quality, with anti-aliased lines and 4x supersampling.
+    pub const ULTRA: Self = Self { supersample: 4, antialias: true };
+}
+
+impl<'py> FromPyObject<'py> for Quality {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        // Integer supersampling factor
+        if let Ok (supersample) = ob.extract::<u32>() {
+            if supersample == 0 {
+                return Err(PyValueError::new_err("supersampling factor must be at least 1"));
+            }
+
+            return Ok(Self {
+                supersample,
+                antialias: true,
+            });
+        }
+
+        // Named preset
+        match ob.extract::<String>()?.as_str() {
+            "draft" => Ok(Self::DRAFT),
+            "normal" => Ok(Self::NORMAL),
+            "high" => Ok(Self::HIGH),
+            "ultra" => Ok(Self::ULTRA),
+            name => Err(PyValueError::new_err(format!("unknown render quality '{}'", name))),
+        }
+    }
+}
+
+/// Transform a progress variable (between 0 and 1) to create smooth transitions.
+fn smooth(progress: f64) -> f64 {
+    0.5 - 0.5 * (progress * PI).cos()
+}
+
+/// Check that the units of a video, if any, are positive.
+fn check_units(units: Option<f64>) -> PyResult<()> {
+    if units.is_some_and(|units| !(units.is_finite() && units > 0.0)) {
+        return Err(PyValueError::new_err("video units must be positive"));
+    }
+
+    Ok(())
+}
 
 #[pyclass]
 /// A video, represented as a series of still frames.
@@ -47,8 +213,11 @@ pub struct Video {
     /// Video height (pixels).
     height: u32,
 
-    /// Background color (RGB).
-    background: Rgb<u8>,
+    /// Background color (RGB), or `None` for the theme's background color.
+    background: Option<Rgb<u8>>,
+
+    /// Image covering the background, if any.
+    background_image: Option<Image>,
 
     #[pyo3(get, set)]
     /// Video frame rate (fps).
@@ -58,103 +227,289 @@ pub struct Video {
     /// Video duration (seconds).
     duration: f64,
 
-    /// Video animations, combined with their location, start frame, and end frame.
+    #[pyo3(get)]
+    /// Height of the frame in world units, or `None` if one world unit is one pixel.
+    units: Option<f64>,
+
+    /// Video animations, combined with their location, start frame, end frame, and layer.
     animations: Vec<Instance>,
+
+    /// Video layers, from bottom to top.
+    layers: Vec<Layer>,
+
+    #[pyo3(get, set)]
+    /// Camera at the start of the video.
+    camera: Camera,
+
+    /// Camera movements, in the order they were added.
+    camera_moves: Vec<CameraMove>,
+
+    #[pyo3(get, set)]
+    /// Theme giving the colors and thickness of objects that don't specify their own.
+    theme: Theme,
 }
 
 #[pymethods]
 impl Video {
     #[new]
+    #[pyo3(signature=(size, background, fps, duration, units=None, theme=None, background_image=None))]
     /// Construct a new video.
+    /// 
+    /// If `units` is given, all geometry is measured in world units, with the height
+    /// of the frame being `units` world units regardless of the video's resolution.
+    /// Otherwise, all geometry is measured in pixels.
+    /// 
+    /// Objects that don't specify a color or thickness take them from the `theme`
+    /// (the dark theme by default), as does the background if it is `None`.
+    /// 
+    /// A `background_image` (the path of a PNG or JPEG file) covers the whole frame
+    /// behind everything else, cropped to keep its aspect ratio.
     pub fn new(
         size: (u32, u32),
-        background: [u8; 3],
+        background: Option<Color>,
         fps: f64,
         duration: f64,
-    ) -> Self {
-        Self {
+        units: Option<f64>,
+        theme: Option<Theme>,
+        background_image: Option<String>,
+    ) -> PyResult<Self> {
+        check_units(units)?;
+
+        Ok(Self {
             width: size.0,
             height: size.1,
-            background: Rgb (background),
+            background: background.map(|color| color.rgb()),
+            background_image: background_image.map(|path| Image::new(path, Vector::zero(), None, None, 0.0, 1.0)).transpose()?,
             fps,
             duration,
+            units,
             animations: Vec::new(),
-        }
+            layers: DEFAULT_LAYERS.iter().map(|name| Layer::new(name, 1.0, true, BlendMode::Normal, Vec::new())).collect(),
+            camera: Camera::default(),
+            camera_moves: Vec::new(),
+            theme: theme.unwrap_or_default(),
+        })
+    }
+
+    #[getter]
+    /// Path of the image covering the background, if any.
+    pub fn get_background_image(&self) -> Option<String> {
+        self.background_image.as_ref().map(Image::get_path)
+    }
+
+    #[setter]
+    /// Set the image covering the background (the path of a PNG or JPEG file, or `None`).
+    pub fn set_background_image(&mut self, path: Option<String>) -> PyResult<()> {
+        self.background_image = path.map(|path| Image::new(path, Vector::zero(), None, None, 0.0, 1.0)).transpose()?;
+
+        Ok(())
+    }
+
+    #[setter]
+    /// Set the height of the frame in world units (`None` if one world unit is one pixel).
+    pub fn set_units(&mut self, units: Option<f64>) -> PyResult<()> {
+        check_units(units)?;
+        self.units = units;
+
+        Ok(())
     }
 
+    #[pyo3(signature=(animation, location, start, end, z=0.0, layer=DEFAULT_LAYER, blend=BlendMode::Normal, effects=Vec::new()))]
     /// Add an animation to this video.
     /// 
     /// Note that `start` and `end` are given in seconds.  These are converted into
     /// frame numbers based on the FPS of the video.
-    pub fn add(&mut self, animation: Animation, location: Vector, start: f64, end: f64) {
+    /// 
+    /// Animations are drawn layer by layer.  Within a layer, animations with a higher
+    /// `z` are drawn on top, and animations with equal `z` are drawn in the order
+    /// they were added.
+    /// 
+    /// The animation is combined with what is beneath it according to `blend`
+    /// (`"normal"`, `"add"`, `"screen"`, `"multiply"`, `"lighten"`, or `"difference"`),
+    /// after applying any `effects` to it.
+    #[allow(clippy::too_many_arguments, reason = "placement options are Python keyword arguments")]
+    pub fn add(&mut self, animation: Animation, location: Vector, start: f64, end: f64, z: f64, layer: &str, blend: BlendMode, effects: Vec<Effect>) -> PyResult<()> {
         // Frame numbers from timestamps
         let start_frame = (start * self.fps) as u32;
         let end_frame = (end * self.fps) as u32;
 
-        self.animations.push((animation, location, start_frame, end_frame));
+        self.animations.push(Instance {
+            animation,
+            location,
+            start: start_frame,
+            end: end_frame,
+            layer: self.layer_index(layer)?,
+            z,
+            blend,
+            effects,
+        });
+
+        Ok(())
+    }
+
+    #[pyo3(signature=(name, opacity=1.0, visible=true, blend=BlendMode::Normal, effects=Vec::new()))]
+    /// Add a new layer on top of all existing layers.
+    /// 
+    /// The layer is drawn on its own, then combined with the layers beneath it
+    /// according to `blend`, after applying any `effects` to the whole layer.
+    pub fn add_layer(&mut self, name: &str, opacity: f64, visible: bool, blend: BlendMode, effects: Vec<Effect>) -> PyResult<()> {
+        if self.layer_index(name).is_ok() {
+            return Err(PyValueError::new_err(format!("layer '{}' already exists", name)));
+        }
+
+        self.layers.push(Layer::new(name, opacity, visible, blend, effects));
+
+        Ok(())
+    }
+
+    #[pyo3(signature=(name, opacity=None, visible=None, blend=None, effects=None))]
+    /// Change the opacity, visibility, blend mode, and/or effects of an existing layer.
+    pub fn set_layer(&mut self, name: &str, opacity: Option<f64>, visible: Option<bool>, blend: Option<BlendMode>, effects: Option<Vec<Effect>>) -> PyResult<()> {
+        let index = self.layer_index(name)?;
+        let layer = &mut self.layers[index];
+
+        if let Some (opacity) = opacity {
+            layer.opacity = opacity.clamp(0.0, 1.0);
+        }
+
+        if let Some (visible) = visible {
+            layer.visible = visible;
+        }
+
+        if let Some (blend) = blend {
+            layer.blend = blend;
+        }
+
+        if let Some (effects) = effects {
+            layer.effects = effects;
+        }
+
+        Ok(())
+    }
+
+    #[getter]
+    /// Names of the layers in this video, from bottom to top.
+    pub fn get_layers(&self) -> Vec<String> {
+        self.layers.iter().map(|layer| layer.name.clone()).collect()
+    }
+
+    /// Smoothly move the camera to a new position, zoom, and rotation.
+    /// 
+    /// Note that `start` and `end` are given in seconds.  These are converted into
+    /// frame numbers based on the FPS of the video.
+    pub fn move_camera(&mut self, camera: Camera, start: f64, end: f64) {
+        self.camera_moves.push(CameraMove {
+            motion: CameraMotion::To (camera),
+            start: (start * self.fps) as u32,
+            end: (end * self.fps) as u32,
+        });
+    }
+
+    /// Move the camera along the outline of a shape, keeping its zoom and rotation.
+    /// 
+    /// The camera follows the shape at the same pace as the shape's `.trace` animation,
+    /// so following and tracing a shape over the same interval keeps the tip of the
+    /// trace at the center of the frame.
+    /// 
+    /// Note that `start` and `end` are given in seconds.  These are converted into
+    /// frame numbers based on the FPS of the video.
+    pub fn follow(&mut self, shape: Shape, location: Vector, start: f64, end: f64) {
+        self.camera_moves.push(CameraMove {
+            motion: CameraMotion::Follow (Box::new(shape), location),
+            start: (start * self.fps) as u32,
+            end: (end * self.fps) as u32,
+        });
     }
 
+    #[pyo3(signature=(shape, location, start, end, z=0.0, layer=DEFAULT_LAYER, blend=BlendMode::Normal, effects=Vec::new()))]
     /// Trace and untrace a shape on this video.
     /// 
     /// Note that `start` and `end` are given in seconds.  These are converted into
     /// frame numbers based on the FPS of the video.  For `Video::trace_untrace`, these
     /// must be at least 2 seconds apart.
-    pub fn add_shape(&mut self, shape: Shape, location: Vector, start: f64, end: f64) {
+    #[allow(clippy::too_many_arguments, reason = "placement options are Python keyword arguments")]
+    pub fn add_shape(&mut self, shape: Shape, location: Vector, start: f64, end: f64, z: f64, layer: &str, blend: BlendMode, effects: Vec<Effect>) -> PyResult<()> {
         if end - start < 2.0 {
-            return;
+            return Ok(());
         }
 
-        self.add(shape.get_trace(), location, start, start + TRACE_TIME);
-        self.add(shape.get_display(), location, start + TRACE_TIME, end - TRACE_TIME);
-        self.add(shape.get_untrace(), location, end - TRACE_TIME, end);
+        self.add(shape.get_trace(), location, start, start + TRACE_TIME, z, layer, blend, effects.clone())?;
+        self.add(shape.get_display(), location, start + TRACE_TIME, end - TRACE_TIME, z, layer, blend, effects.clone())?;
+        self.add(shape.get_untrace(), location, end - TRACE_TIME, end, z, layer, blend, effects)
     }
 
+    #[pyo3(signature=(parametric, location, start, z=0.0, layer=DEFAULT_LAYER, blend=BlendMode::Normal, effects=Vec::new()))]
     /// Create a "flow" effect according to a parametric curve.
     /// 
     /// Note that `start` and `end` are given in seconds.  These are converted into
     /// frame numbers based on the FPS of the video.  For `Video::trace_untrace`, these
     /// must be at least 2 seconds apart.
-    pub fn flow(&mut self, parametric: Parametric, location: Vector, start: f64) {
-        self.add(parametric.get_trace(), location, start, start + TRACE_TIME*0.5);
-        self.add(parametric.get_untrace(), location, start + TRACE_TIME*0.5, start + TRACE_TIME);
+    #[allow(clippy::too_many_arguments, reason = "placement options are Python keyword arguments")]
+    pub fn flow(&mut self, parametric: Parametric, location: Vector, start: f64, z: f64, layer: &str, blend: BlendMode, effects: Vec<Effect>) -> PyResult<()> {
+        self.add(parametric.get_trace(), location, start, start + TRACE_TIME*0.5, z, layer, blend, effects.clone())?;
+        self.add(parametric.get_untrace(), location, start + TRACE_TIME*0.5, start + TRACE_TIME, z, layer, blend, effects)
     }
 
+    #[pyo3(signature=(parametric, location, start, end, z=0.0, layer=DEFAULT_LAYER, blend=BlendMode::Normal, effects=Vec::new()))]
     /// Trace and untrace a parametric on this video.
     /// 
     /// Note that `start` and `end` are given in seconds.  These are converted into
     /// frame numbers based on the FPS of the video.  For `Video::trace_untrace`, these
     /// must be at least 2 seconds apart.
-    pub fn add_parametric(&mut self, parametric: Parametric, location: Vector, start: f64, end: f64) {
+    #[allow(clippy::too_many_arguments, reason = "placement options are Python keyword arguments")]
+    pub fn add_parametric(&mut self, parametric: Parametric, location: Vector, start: f64, end: f64, z: f64, layer: &str, blend: BlendMode, effects: Vec<Effect>) -> PyResult<()> {
         if end - start < 2.0 {
-            return;
+            return Ok(());
         }
 
-        self.add(parametric.get_trace(), location, start, start + TRACE_TIME);
-        self.add(parametric.get_display(), location, start + TRACE_TIME, end - TRACE_TIME);
-        self.add(parametric.get_untrace(), location, end - TRACE_TIME, end);
+        self.add(parametric.get_trace(), location, start, start + TRACE_TIME, z, layer, blend, effects.clone())?;
+        self.add(parametric.get_display(), location, start + TRACE_TIME, end - TRACE_TIME, z, layer, blend, effects.clone())?;
+        self.add(parametric.get_untrace(), location, end - TRACE_TIME, end, z, layer, blend, effects)
     }
 
+    #[pyo3(signature=(linear_axes, location, start, end, z=0.0, layer="background", blend=BlendMode::Normal, effects=Vec::new()))]
     /// Trace and untrace linear axes on this video.
     /// 
     /// Note that `start` and `end` are given in seconds.  These are converted into
     /// frame numbers based on the FPS of the video.  For `Video::trace_untrace`, these
     /// must be at least 2 seconds apart.
-    pub fn add_axes(&mut self, linear_axes: LinearAxes, location: Vector, start: f64, end: f64) {
+    /// 
+    /// Axes are placed on the background layer by default, so that they always sit
+    /// beneath curves plotted on them.
+    #[allow(clippy::too_many_arguments, reason = "placement options are Python keyword arguments")]
+    pub fn add_axes(&mut self, linear_axes: LinearAxes, location: Vector, start: f64, end: f64, z: f64, layer: &str, blend: BlendMode, effects: Vec<Effect>) -> PyResult<()> {
         if end - start < 2.0 {
-            return;
+            return Ok(());
         }
 
-        self.add(linear_axes.get_trace(), location, start, start + TRACE_TIME);
-        self.add(linear_axes.get_display(), location, start + TRACE_TIME, end - TRACE_TIME);
-        self.add(linear_axes.get_untrace(), location, end - TRACE_TIME, end);
+        self.add(linear_axes.get_trace(), location, start, start + TRACE_TIME, z, layer, blend, effects.clone())?;
+        self.add(linear_axes.get_display(), location, start + TRACE_TIME, end - TRACE_TIME, z, layer, blend, effects.clone())?;
+        self.add(linear_axes.get_untrace(), location, end - TRACE_TIME, end, z, layer, blend, effects)
     }
 
-    #[pyo3(signature=(output_dir, threads=1))]
+    #[pyo3(signature=(output_dir, threads=1, quality=Quality::NORMAL, compositing=Compositing::Linear))]
     /// Render this video from a series of still frames.
-    pub fn render(&self, output_dir: String, threads: usize) {
+    /// 
+    /// The `quality` of the render is either a preset (`"draft"`, `"normal"`, `"high"`,
+    /// or `"ultra"`) or an integer supersampling factor.  Supersampled frames are
+    /// rendered at a multiple of the output resolution, then downsampled, giving
+    /// smoother edges at the cost of speed.
+    /// 
+    /// Colors are blended in linear light by default.  With `"legacy"` compositing,
+    /// gamma-encoded sRGB values are blended directly instead.
+    /// 
+    /// Parametric curves without a tolerance of their own are sampled again first, to the
+    /// default tolerance in this video's pixels.
+    pub fn render(&self, output_dir: String, threads: usize, quality: Quality, compositing: Compositing) -> PyResult<()> {
         // How many frames?
         let frame_count = (self.duration * self.fps) as u32;
 
+        // Prepare every animation (while Python functions can still be called) for the size of a pixel
+        let mut prepared = self.animations.clone();
+        for instance in &mut prepared {
+            instance.animation.0.prepare(1.0 / self.scale())?;
+        }
+
         // Progress bar style
         let style = ProgressStyle::with_template(
             "[{elapsed_precise}] {wide_bar} {pos:>7}/{len:7} frames [ETA {eta_precise}]"
@@ -170,40 +525,144 @@ impl Video {
         // Create thread pool
         ThreadPoolBuilder::new().num_threads(threads).build_global().unwrap();
 
+        // Sort animations by layer, then by depth (stable, so ties keep insertion order)
+        let mut instances: Vec<&Instance> = prepared.iter().collect();
+        instances.sort_by(|a, b| a.layer.cmp(&b.layer).then(a.z.total_cmp(&b.z)));
+
+        // Theme of every frame, with this video's background
+        let mut theme = self.theme.clone();
+        if let Some (background) = self.background {
+            theme.background = background;
+        }
+
         // Render in parallel
         (0..frame_count).into_par_iter().for_each(|k| {
             // New, empty frame
-            let mut frame = RgbImage::new(self.width, self.height);
+            let mut frame = Frame::new(
+                (self.width * quality.supersample, self.height * quality.supersample),
+                theme.clone(),
+                self.camera_at(k),
+                self.scale() * quality.supersample as f64,
+                self.units.map(|units| units / self.height as f64),
+                quality.antialias,
+                compositing,
+            );
+
+            // Cover the background with its image
+            if let Some (image) = &self.background_image {
+                image.cover(&mut frame);
+            }
 
-            // Create background
-            for i in 0..self.width {
-                for j in 0..self.height {
-                    frame.put_pixel(i, j, self.background);
+            for (l, layer) in self.layers.iter().enumerate() {
+                // Skip hidden and fully transparent layers
+                if !layer.visible || layer.opacity == 0.0 {
+                    continue;
                 }
-            }
 
-            for (animation, location, start, end) in &self.animations {
-                // Determine progress of this animation
-                let progress = (k as f64 - *start as f64) / (*end as f64 - *start as f64);
+                // Layers with a blend mode or effects are drawn offscreen, then blended onto the frame
+                let offscreen_layer = layer.blend != BlendMode::Normal || !layer.effects.is_empty();
+                let mut offscreen = offscreen_layer.then(|| frame.offscreen());
+
+                // Otherwise, keep a copy of the frame beneath this layer, if it will be blended
+                let beneath = if offscreen.is_none() && layer.opacity < 1.0 {
+                    Some (frame.image.clone())
+                } else {
+                    None
+                };
+
+                let canvas = offscreen.as_mut().unwrap_or(&mut frame);
+
+                for instance in instances.iter().filter(|instance| instance.layer == l) {
+                    // Determine progress of this animation
+                    let progress = (k as f64 - instance.start as f64) / (instance.end as f64 - instance.start as f64);
+
+                    if (0.0..=1.0).contains(&progress) {
+                        // Construct visual artist from this animation
+                        let artist = instance.animation.0.play(smooth(progress));
+
+                        // Draw on this frame, with this animation's blend mode and effects
+                        canvas.draw(artist.as_ref(), instance.location, instance.blend, &instance.effects);
+                    }
+                }
 
-                if 0.0 <= progress && progress <= 1.0 {
-                    // Transform the progress variable to create smooth transitions
-                    let progress_transform = 0.5 - 0.5 * (progress * PI).cos();
+                // Blend this layer with the frame beneath it
+                if let Some (mut offscreen) = offscreen {
+                    for effect in &layer.effects {
+                        effect.apply(&mut offscreen);
+                    }
 
-                    // Construct visual artist from this animation
-                    let artist = animation.0.play(progress_transform);
+                    frame.composite(&offscreen, layer.opacity, layer.blend);
+                } else if let Some (mut beneath) = beneath {
+                    for (x, y, pixel) in frame.image.enumerate_pixels() {
+                        add_pixel(&mut beneath, x, y, *pixel, layer.opacity, compositing, BlendMode::Normal);
+                    }
 
-                    // Draw on this frame
-                    artist.draw(*location, &mut frame);
+                    frame.image = beneath;
                 }
             }
 
-            frame.save(format!("{}/frame_{:04}.png", output_dir, k)).unwrap();
+            frame.downsample(quality.supersample).save(format!("{}/frame_{:04}.png", output_dir, k)).unwrap();
 
             // Increment progress bar
             bar.inc(1);
         });
 
         bar.finish();
+
+        Ok(())
+    }
+}
+
+impl Video {
+    /// Number of pixels per world unit.
+    fn scale(&self) -> f64 {
+        match self.units {
+            Some (units) => self.height as f64 / units,
+            None => 1.0,
+        }
+    }
+
+    /// Find the index of the layer with the given name.
+    fn layer_index(&self, name: &str) -> PyResult<usize> {
+        self.layers.iter()
+            .position(|layer| layer.name == name)
+            .ok_or_else(|| PyValueError::new_err(format!("no layer named '{}'", name)))
+    }
+
+    /// Determine the state of the camera at a given frame.
+    fn camera_at(&self, frame: u32) -> Camera {
+        let mut camera = self.camera;
+
+        // Apply camera movements in order of their start frames (stable, so ties keep insertion order)
+        let mut moves: Vec<&CameraMove> = self.camera_moves.iter().collect();
+        moves.sort_by_key(|m| m.start);
+
+        for m in moves {
+            // Movements that have not started yet have no effect
+            if frame < m.start {
+                continue;
+            }
+
+            // Determine progress of this movement
+            let progress = if m.end > m.start {
+                ((frame - m.start) as f64 / (m.end - m.start) as f64).min(1.0)
+            } else {
+                1.0
+            };
+
+            match &m.motion {
+                CameraMotion::To (target) => {
+                    camera = camera.lerp(*target, smooth(progress));
+                },
+                CameraMotion::Follow (shape, location) => {
+                    // Fix floating-point errors
+                    let t = smooth(progress).clamp(0.0, 1.0 - STEP);
+
+                    camera.position = *location + shape.trace(t);
+                },
+            }
+        }
+
+        camera
     }
 }
\ No newline at end of file